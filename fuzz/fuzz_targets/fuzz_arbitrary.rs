@@ -0,0 +1,15 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate httpdate;
+
+use std::time::SystemTime;
+
+use httpdate::HttpDate;
+
+fuzz_target!(|dates: (HttpDate, HttpDate)| {
+    let (a, b) = dates;
+    let s = a.to_string();
+    assert_eq!(s.parse::<HttpDate>().expect("formatting to round trip"), a);
+    assert_eq!(HttpDate::from(SystemTime::from(a)), a);
+    assert_eq!(a.cmp(&b), SystemTime::from(a).cmp(&SystemTime::from(b)));
+});
@@ -0,0 +1,43 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate chrono;
+extern crate httpdate;
+
+use std::str;
+use std::time::UNIX_EPOCH;
+
+use chrono::DateTime;
+use httpdate::parse_http_date;
+
+// Compare with chrono's RFC 2822 parser on the inputs both support:
+// IMF-fixdate is a subset of the RFC 2822 date format.
+fuzz_target!(|data: &[u8]| {
+    let s = match str::from_utf8(data) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let ours = parse_http_date(s)
+        .ok()
+        .map(|d| d.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64);
+
+    // Every IMF-fixdate we accept is a valid RFC 2822 date.
+    let trimmed = s.trim();
+    if let Some(secs) = ours {
+        if trimmed.len() == 29 && trimmed.as_bytes()[3] == b',' {
+            let theirs = DateTime::parse_from_rfc2822(trimmed)
+                .expect("chrono to accept IMF-fixdate");
+            assert_eq!(theirs.timestamp(), secs);
+        }
+    }
+
+    // Every RFC 2822 date in the exact IMF-fixdate form is accepted by us.
+    // Leap seconds and dates before 1970 are out of our range.
+    if let Ok(dt) = DateTime::parse_from_rfc2822(s) {
+        if dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string() == s
+            && dt.timestamp() >= 0
+            && dt.timestamp_subsec_nanos() == 0
+        {
+            assert_eq!(ours, Some(dt.timestamp()));
+        }
+    }
+});
@@ -0,0 +1,85 @@
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use core::time::Duration;
+
+use crate::{delta_seconds, Error};
+
+/// Value of the `Age` header field.
+///
+/// The age is the time in seconds since the response was generated or
+/// validated by the origin server, see RFC 9111 section 5.1. Values larger
+/// than 2<sup>31</sup> seconds are clamped to 2<sup>31</sup> as the
+/// specification requires.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::Age;
+/// let age: Age = "99999999999999".parse().unwrap();
+/// assert_eq!(Duration::from(age), Duration::from_secs(2147483648));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Age(u64);
+
+impl Age {
+    /// The largest representable age, 2<sup>31</sup> seconds.
+    pub const MAX: Age = Age(delta_seconds::MAX_SECS);
+
+    /// Create an age from seconds, clamping it to `Age::MAX`.
+    pub fn from_secs(secs: u64) -> Age {
+        Age(secs.min(delta_seconds::MAX_SECS))
+    }
+
+    /// The age in seconds.
+    pub fn as_secs(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for Age {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Age, Error> {
+        delta_seconds::parse_secs(s.trim().as_bytes()).map(Age)
+    }
+}
+
+impl Display for Age {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Age> for Duration {
+    fn from(v: Age) -> Duration {
+        Duration::from_secs(v.0)
+    }
+}
+
+impl From<Duration> for Age {
+    /// Truncates to whole seconds and clamps to `Age::MAX`.
+    fn from(v: Duration) -> Age {
+        Age::from_secs(v.as_secs())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Age;
+
+    #[test]
+    fn test_age() {
+        let age: Age = "60".parse().unwrap();
+        assert_eq!(age.as_secs(), 60);
+        assert_eq!(Duration::from(age), Duration::from_secs(60));
+        assert_eq!(age.to_string(), "60");
+        assert_eq!("2147483648".parse(), Ok(Age::MAX));
+        assert_eq!("99999999999999999999999999".parse(), Ok(Age::MAX));
+        assert_eq!(Age::from(Duration::from_millis(1999)), Age::from_secs(1));
+        assert_eq!(Age::from_secs(u64::MAX), Age::MAX);
+        assert!("1.5".parse::<Age>().is_err());
+        assert!("".parse::<Age>().is_err());
+    }
+}
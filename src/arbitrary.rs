@@ -0,0 +1,36 @@
+//! `Arbitrary` implementation, enabled with the `arbitrary` feature.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::date::MAX_SECS;
+use crate::HttpDate;
+
+impl<'a> Arbitrary<'a> for HttpDate {
+    /// Generates dates between 1970 and 9999 with a matching weekday.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<HttpDate> {
+        let secs = u.int_in_range(0..=MAX_SECS - 1)?;
+        Ok(HttpDate::from_secs_since_epoch(secs).expect("seconds are in range"))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <u64 as Arbitrary>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    use crate::HttpDate;
+
+    #[test]
+    fn test_arbitrary_round_trip() {
+        let data: Vec<u8> = (0..=255).cycle().step_by(7).take(512).collect();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let d = HttpDate::arbitrary(&mut u).unwrap();
+            assert_eq!(d.to_string().parse::<HttpDate>(), Ok(d));
+        }
+    }
+}
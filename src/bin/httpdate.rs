@@ -0,0 +1,109 @@
+//! Convert between epoch seconds, RFC 3339 timestamps and HTTP dates.
+//!
+//! Install with `cargo install httpdate --features cli`.
+
+use std::convert::TryFrom;
+use std::env;
+use std::io::{self, BufRead};
+use std::process;
+
+use httpdate::{Error, HttpDate, UnixTimestamp};
+
+const USAGE: &str = "\
+Usage: httpdate [--check] [VALUE]...
+
+Convert dates between epoch seconds, RFC 3339 timestamps and HTTP dates.
+Prints the seconds since the epoch, the RFC 3339 timestamp and the
+IMF-fixdate of each value, separated by tabs. Values are read from
+standard input, one per line, if none are given. `now` is the current time.
+
+Options:
+  --check     Only check that the values are valid HTTP dates
+  -h, --help  Print this help
+";
+
+fn main() {
+    let mut check = false;
+    let mut values = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with('-') && arg.parse::<i64>().is_err() => {
+                eprint!("unknown option: {}\n\n{}", arg, USAGE);
+                process::exit(2);
+            }
+            _ => values.push(arg),
+        }
+    }
+    if values.is_empty() {
+        for line in io::stdin().lock().lines() {
+            values.push(line.unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                process::exit(2);
+            }));
+        }
+    }
+
+    let mut failed = false;
+    for value in &values {
+        let result = if check {
+            value.parse::<HttpDate>()
+        } else {
+            convert(value)
+        };
+        match result {
+            Ok(_) if check => println!("ok\t{}", value),
+            Ok(date) => println!(
+                "{}\t{}\t{}",
+                UnixTimestamp::from(date).0,
+                date.rfc3339(),
+                date
+            ),
+            Err(e) => {
+                failed = true;
+                eprintln!("{}: {}", value, e);
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Read epoch seconds, an RFC 3339 timestamp or an HTTP date.
+fn convert(value: &str) -> Result<HttpDate, Error> {
+    let value = value.trim();
+    if value == "now" {
+        return Ok(HttpDate::now());
+    }
+    if let Ok(secs) = value.parse::<i64>() {
+        return HttpDate::try_from(UnixTimestamp(secs));
+    }
+    HttpDate::parse_rfc3339(value).or_else(|_| value.parse())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert;
+
+    #[test]
+    fn test_convert() {
+        for value in &[
+            "784111777",
+            " 1994-11-06T08:49:37Z ",
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            let date = convert(value).unwrap();
+            assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        }
+        assert!(convert("now").is_ok());
+        assert!(convert("-1").unwrap_err().is_out_of_range());
+        assert!(convert("yesterday").is_err());
+    }
+}
@@ -0,0 +1,510 @@
+//! Calendar functions for the proleptic Gregorian calendar.
+//!
+//! ```
+//! use httpdate::calendar::{days_in_month, is_leap_year, weekday_of, Weekday};
+//!
+//! assert!(is_leap_year(2000));
+//! assert_eq!(days_in_month(2100, 2), Some(28));
+//! assert_eq!(weekday_of(1994, 11, 6), Some(Weekday::Sunday));
+//! ```
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::error::{Error, ErrorKind};
+use crate::rfc3339::from_local;
+use crate::HttpDate;
+
+/// A day of the week.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Weekday {
+    /// Monday.
+    Monday,
+    /// Tuesday.
+    Tuesday,
+    /// Wednesday.
+    Wednesday,
+    /// Thursday.
+    Thursday,
+    /// Friday.
+    Friday,
+    /// Saturday.
+    Saturday,
+    /// Sunday.
+    Sunday,
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+const SHORT_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+impl Weekday {
+    /// The weekday with Monday as 1 and Sunday as 7, `None` otherwise.
+    pub fn from_number(n: u8) -> Option<Weekday> {
+        WEEKDAYS.get(usize::from(n).wrapping_sub(1)).copied()
+    }
+
+    /// The number of the weekday, Monday is 1 and Sunday is 7.
+    pub fn number(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// The three letter name used in HTTP dates, like `Sun`.
+    pub fn short_name(self) -> &'static str {
+        SHORT_NAMES[self as usize]
+    }
+}
+
+impl Display for Weekday {
+    /// Formats the three letter name.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.short_name())
+    }
+}
+
+/// Whether February has 29 days in `year`.
+pub fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// The number of days in a month from 1 to 12, `None` for other months.
+pub fn days_in_month(year: u16, month: u8) -> Option<u8> {
+    if (1..=12).contains(&month) {
+        Some(month_len(year, month))
+    } else {
+        None
+    }
+}
+
+/// The weekday of a date, `None` if there is no such date.
+pub fn weekday_of(year: u16, month: u8, day: u8) -> Option<Weekday> {
+    if day == 0 || day > days_in_month(year, month)? {
+        return None;
+    }
+    let days = days_from_civil(i64::from(year), i64::from(month), i64::from(day));
+    // 1970-01-01 was a Thursday.
+    Some(WEEKDAYS[(days + 3).rem_euclid(7) as usize])
+}
+
+impl HttpDate {
+    /// The start of a day, 00:00:00 GMT.
+    ///
+    /// Fails if the month or the day does not exist or the date is not
+    /// between 1970 and 9999.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::midnight(1994, 11, 6).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 00:00:00 GMT");
+    /// assert!(HttpDate::midnight(2023, 2, 29).unwrap_err().is_invalid_date());
+    /// assert!(HttpDate::midnight(1969, 12, 31).unwrap_err().is_out_of_range());
+    /// ```
+    pub fn midnight(year: u16, month: u8, day: u8) -> Result<HttpDate, Error> {
+        from_local(
+            i64::from(year),
+            i64::from(month),
+            i64::from(day),
+            0,
+            0,
+            0,
+            0,
+        )
+    }
+
+    /// The year, month from 1 to 12 and day of the month.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.date(), (1994, 11, 6));
+    /// ```
+    pub fn date(&self) -> (u16, u8, u8) {
+        let f = self.fields();
+        (f.year, f.mon, f.day)
+    }
+
+    /// The hour, minute and second.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.time(), (8, 49, 37));
+    /// ```
+    pub fn time(&self) -> (u8, u8, u8) {
+        let secs = self.secs_since_epoch() % 86400;
+        (
+            (secs / 3600) as u8,
+            (secs / 60 % 60) as u8,
+            (secs % 60) as u8,
+        )
+    }
+
+    /// Split the date into `(year, month, day, hour, min, sec, nsec)`, the
+    /// tuple used by the `datealgo` crate. The nanoseconds are always 0.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.to_parts(), (1994, 11, 6, 8, 49, 37, 0));
+    /// ```
+    pub fn to_parts(&self) -> (i32, u8, u8, u8, u8, u8, u32) {
+        let (year, mon, day) = self.date();
+        let (hour, min, sec) = self.time();
+        (i32::from(year), mon, day, hour, min, sec, 0)
+    }
+
+    /// Create a date from a `(year, month, day, hour, min, sec, nsec)`
+    /// tuple as used by the `datealgo` crate.
+    ///
+    /// Nanoseconds are truncated. Fails if a field is out of range or the
+    /// day does not exist in the month.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::from_parts((1994, 11, 6, 8, 49, 37, 500_000_000)).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    pub fn from_parts(parts: (i32, u8, u8, u8, u8, u8, u32)) -> Result<HttpDate, Error> {
+        let (year, mon, day, hour, min, sec, nsec) = parts;
+        if nsec >= 1_000_000_000 || !(1970..=9999).contains(&year) {
+            return Err(Error::new(ErrorKind::Range, None));
+        }
+        from_local(
+            i64::from(year),
+            i64::from(mon),
+            i64::from(day),
+            i64::from(hour),
+            i64::from(min),
+            i64::from(sec),
+            0,
+        )
+    }
+
+    /// The same day at another time of day.
+    ///
+    /// Fails if the hour, minute or second is out of range.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// let end_of_day = date.with_time(23, 59, 59).unwrap();
+    /// assert_eq!(end_of_day.to_string(), "Sun, 06 Nov 1994 23:59:59 GMT");
+    /// assert!(date.with_time(24, 0, 0).unwrap_err().is_out_of_range());
+    /// ```
+    pub fn with_time(&self, hour: u8, min: u8, sec: u8) -> Result<HttpDate, Error> {
+        let f = self.fields();
+        from_local(
+            i64::from(f.year),
+            i64::from(f.mon),
+            i64::from(f.day),
+            i64::from(hour),
+            i64::from(min),
+            i64::from(sec),
+            0,
+        )
+    }
+
+    /// The same time on the following day, `None` after year 9999.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Wed, 28 Feb 2024 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.next_day().unwrap().to_string(), "Thu, 29 Feb 2024 08:49:37 GMT");
+    /// ```
+    pub fn next_day(&self) -> Option<HttpDate> {
+        HttpDate::from_secs_since_epoch(self.secs_since_epoch() + 86400)
+    }
+
+    /// The same time on the day before, `None` before 1970.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Fri, 01 Mar 2024 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.previous_day().unwrap().to_string(), "Thu, 29 Feb 2024 08:49:37 GMT");
+    /// ```
+    pub fn previous_day(&self) -> Option<HttpDate> {
+        HttpDate::from_secs_since_epoch(self.secs_since_epoch().checked_sub(86400)?)
+    }
+
+    /// The same day and time in the following month, `None` after year 9999.
+    ///
+    /// The day is clamped to the last day of shorter months.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Wed, 31 Jan 2024 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.next_month().unwrap().to_string(), "Thu, 29 Feb 2024 08:49:37 GMT");
+    /// ```
+    pub fn next_month(&self) -> Option<HttpDate> {
+        let f = self.fields();
+        let (year, mon) = if f.mon == 12 {
+            (f.year + 1, 1)
+        } else {
+            (f.year, f.mon + 1)
+        };
+        from_local(
+            i64::from(year),
+            i64::from(mon),
+            i64::from(f.day.min(month_len(year, mon))),
+            i64::from(f.hour),
+            i64::from(f.min),
+            i64::from(f.sec),
+            0,
+        )
+        .ok()
+    }
+
+    /// The ISO 8601 week-numbering year and week from 1 to 53.
+    ///
+    /// Weeks start on Monday and the first week of a year contains its
+    /// first Thursday, so days around New Year may belong to the previous
+    /// or next year.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.iso_week(), (1994, 44));
+    /// let date: HttpDate = "Fri, 01 Jan 2021 00:00:00 GMT".parse().unwrap();
+    /// assert_eq!(date.iso_week(), (2020, 53));
+    /// ```
+    pub fn iso_week(&self) -> (u16, u8) {
+        let wday = u64::from(self.fields().wday);
+        // The Thursday of the same week decides the year. It is always in
+        // range as 1970-01-01 was a Thursday and 9999-12-31 a Friday.
+        let thursday = self.secs_since_epoch() / 86400 + 4 - wday;
+        let year = HttpDate::from_secs_since_epoch(thursday * 86400)
+            .expect("the Thursday is in range")
+            .fields()
+            .year;
+        let jan1 = days_from_civil(i64::from(year), 1, 1) as u64;
+        (year, ((thursday - jan1) / 7 + 1) as u8)
+    }
+
+    /// The midnight of every day from this date up to `end`, which is
+    /// excluded.
+    ///
+    /// The first item is the start of the day containing this date, the
+    /// iterator is empty if `end` is not after it.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let start: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// let end: HttpDate = "Tue, 08 Nov 1994 00:00:00 GMT".parse().unwrap();
+    /// let days: Vec<String> = start.days_until(&end).map(|d| d.to_string()).collect();
+    /// assert_eq!(days, ["Sun, 06 Nov 1994 00:00:00 GMT", "Mon, 07 Nov 1994 00:00:00 GMT"]);
+    /// ```
+    pub fn days_until(&self, end: &HttpDate) -> impl Iterator<Item = HttpDate> {
+        let start = self.secs_since_epoch() / 86400 * 86400;
+        (start..end.secs_since_epoch())
+            .step_by(86400)
+            .map(|secs| HttpDate::from_secs_since_epoch(secs).expect("before the end"))
+    }
+}
+
+/// Number of days in a month, the month must be valid.
+pub(crate) fn month_len(year: u16, mon: u8) -> u8 {
+    match mon {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the epoch of a date in the proleptic Gregorian calendar.
+pub(crate) fn days_from_civil(year: i64, mon: i64, day: i64) -> i64 {
+    let y = if mon <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (mon + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use super::{days_in_month, is_leap_year, weekday_of, Weekday};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_calendar() {
+        assert!(is_leap_year(2000) && is_leap_year(2024) && is_leap_year(0));
+        assert!(!is_leap_year(1900) && !is_leap_year(2023));
+        let lens: Vec<_> = (0..=13).map(|m| days_in_month(2024, m)).collect();
+        assert_eq!(lens[0], None);
+        let total: u16 = lens[1..13].iter().map(|l| u16::from(l.unwrap())).sum();
+        assert_eq!(total, 366);
+        assert_eq!(lens[13], None);
+        assert_eq!(days_in_month(2023, 2), Some(28));
+
+        assert_eq!(weekday_of(1970, 1, 1), Some(Weekday::Thursday));
+        assert_eq!(weekday_of(1969, 12, 31), Some(Weekday::Wednesday));
+        assert_eq!(weekday_of(2000, 2, 29), Some(Weekday::Tuesday));
+        assert_eq!(weekday_of(9999, 12, 31), Some(Weekday::Friday));
+        assert_eq!(weekday_of(2023, 2, 29), None);
+        assert_eq!(weekday_of(2023, 1, 0), None);
+        assert_eq!(weekday_of(2023, 0, 1), None);
+
+        for n in 1..=7 {
+            let w = Weekday::from_number(n).unwrap();
+            assert_eq!(w.number(), n);
+        }
+        assert_eq!(Weekday::from_number(0), None);
+        assert_eq!(Weekday::from_number(8), None);
+        assert_eq!(Weekday::Sunday.to_string(), "Sun");
+    }
+
+    #[test]
+    fn test_midnight() {
+        assert_eq!(HttpDate::midnight(1970, 1, 1), Ok(HttpDate::MIN));
+        assert_eq!(
+            HttpDate::midnight(2024, 2, 29).unwrap().to_string(),
+            "Thu, 29 Feb 2024 00:00:00 GMT"
+        );
+        assert_eq!(
+            HttpDate::midnight(9999, 12, 31).unwrap().to_string(),
+            "Fri, 31 Dec 9999 00:00:00 GMT"
+        );
+        assert!(HttpDate::midnight(2024, 4, 31)
+            .unwrap_err()
+            .is_invalid_date());
+        assert!(HttpDate::midnight(2024, 1, 0)
+            .unwrap_err()
+            .is_invalid_date());
+        assert!(HttpDate::midnight(2024, 13, 1)
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(HttpDate::midnight(10000, 1, 1)
+            .unwrap_err()
+            .is_out_of_range());
+    }
+
+    #[test]
+    fn test_day_navigation() {
+        let date: HttpDate = "Sun, 31 Dec 2023 23:59:59 GMT".parse().unwrap();
+        let next = date.next_day().unwrap();
+        assert_eq!(next.to_string(), "Mon, 01 Jan 2024 23:59:59 GMT");
+        assert_eq!(next.previous_day(), Some(date));
+        assert_eq!(
+            date.next_month().unwrap().to_string(),
+            "Wed, 31 Jan 2024 23:59:59 GMT"
+        );
+        let date: HttpDate = "Thu, 31 Oct 2024 00:00:00 GMT".parse().unwrap();
+        assert_eq!(
+            date.next_month().unwrap().to_string(),
+            "Sat, 30 Nov 2024 00:00:00 GMT"
+        );
+        let date: HttpDate = "Tue, 31 Jan 2023 00:00:00 GMT".parse().unwrap();
+        assert_eq!(
+            date.next_month().unwrap().to_string(),
+            "Tue, 28 Feb 2023 00:00:00 GMT"
+        );
+        assert_eq!(HttpDate::MIN.previous_day(), None);
+        assert_eq!(HttpDate::MAX.next_day(), None);
+        assert_eq!(HttpDate::MAX.next_month(), None);
+        assert_eq!(
+            HttpDate::MAX.previous_day().unwrap().next_day(),
+            Some(HttpDate::MAX)
+        );
+    }
+
+    #[test]
+    fn test_with_time() {
+        let date: HttpDate = "Fri, 31 Dec 9999 12:00:00 GMT".parse().unwrap();
+        assert_eq!(date.with_time(23, 59, 59), Ok(HttpDate::MAX));
+        assert_eq!(
+            date.with_time(0, 0, 0).unwrap().to_string(),
+            "Fri, 31 Dec 9999 00:00:00 GMT"
+        );
+        assert!(date.with_time(0, 60, 0).unwrap_err().is_out_of_range());
+        assert!(date.with_time(0, 0, 60).unwrap_err().is_out_of_range());
+    }
+
+    #[test]
+    fn test_date_time() {
+        assert_eq!(HttpDate::MIN.date(), (1970, 1, 1));
+        assert_eq!(HttpDate::MIN.time(), (0, 0, 0));
+        assert_eq!(HttpDate::MAX.date(), (9999, 12, 31));
+        assert_eq!(HttpDate::MAX.time(), (23, 59, 59));
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(HttpDate::MIN.to_parts(), (1970, 1, 1, 0, 0, 0, 0));
+        assert_eq!(HttpDate::MAX.to_parts(), (9999, 12, 31, 23, 59, 59, 0));
+        assert_eq!(
+            HttpDate::from_parts((9999, 12, 31, 23, 59, 59, 999_999_999)),
+            Ok(HttpDate::MAX)
+        );
+        let date: HttpDate = "Thu, 29 Feb 2024 12:30:45 GMT".parse().unwrap();
+        assert_eq!(HttpDate::from_parts(date.to_parts()), Ok(date));
+        assert!(HttpDate::from_parts((2023, 2, 29, 0, 0, 0, 0))
+            .unwrap_err()
+            .is_invalid_date());
+        for parts in &[
+            (1969, 12, 31, 23, 59, 59, 0),
+            (10000, 1, 1, 0, 0, 0, 0),
+            (-1, 1, 1, 0, 0, 0, 0),
+            (2024, 1, 1, 0, 0, 0, 1_000_000_000),
+        ] {
+            assert!(HttpDate::from_parts(*parts).unwrap_err().is_out_of_range());
+        }
+    }
+
+    #[test]
+    fn test_days_until() {
+        let start: HttpDate = "Wed, 28 Feb 2024 23:59:59 GMT".parse().unwrap();
+        let end: HttpDate = "Fri, 01 Mar 2024 00:00:01 GMT".parse().unwrap();
+        let days: Vec<_> = start.days_until(&end).map(|d| d.to_string()).collect();
+        assert_eq!(
+            days,
+            [
+                "Wed, 28 Feb 2024 00:00:00 GMT",
+                "Thu, 29 Feb 2024 00:00:00 GMT",
+                "Fri, 01 Mar 2024 00:00:00 GMT",
+            ]
+        );
+        assert_eq!(start.days_until(&start).count(), 1);
+        assert_eq!(end.days_until(&start).count(), 0);
+        assert_eq!(HttpDate::MIN.days_until(&HttpDate::MAX).count(), 2932897);
+        assert_eq!(
+            HttpDate::MIN
+                .days_until(&HttpDate::MAX)
+                .last()
+                .unwrap()
+                .to_string(),
+            "Fri, 31 Dec 9999 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_iso_week() {
+        for &(date, week) in &[
+            ("Thu, 01 Jan 1970 00:00:00 GMT", (1970, 1)),
+            ("Sun, 04 Jan 1970 23:59:59 GMT", (1970, 1)),
+            ("Mon, 05 Jan 1970 00:00:00 GMT", (1970, 2)),
+            ("Sat, 01 Jan 2000 00:00:00 GMT", (1999, 52)),
+            ("Mon, 03 Jan 2000 00:00:00 GMT", (2000, 1)),
+            ("Sun, 03 Jan 2021 12:00:00 GMT", (2020, 53)),
+            ("Mon, 29 Dec 2025 00:00:00 GMT", (2026, 1)),
+            ("Thu, 31 Dec 2026 00:00:00 GMT", (2026, 53)),
+            ("Fri, 31 Dec 9999 23:59:59 GMT", (9999, 52)),
+        ] {
+            assert_eq!(
+                date.parse::<HttpDate>().unwrap().iso_week(),
+                week,
+                "{}",
+                date
+            );
+        }
+    }
+}
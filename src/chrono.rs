@@ -0,0 +1,72 @@
+//! Conversions from and to chrono types, enabled with the `chrono` feature.
+//!
+//! Converting to `HttpDate` truncates sub-second precision and fails for
+//! dates before 1970 or after 9999. `NaiveDateTime` values are treated as UTC.
+
+use core::convert::TryFrom;
+
+use ::chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+impl From<HttpDate> for DateTime<Utc> {
+    fn from(v: HttpDate) -> DateTime<Utc> {
+        DateTime::from_timestamp(v.secs_since_epoch() as i64, 0)
+            .expect("all HTTP dates are valid chrono dates")
+    }
+}
+
+impl TryFrom<DateTime<Utc>> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: DateTime<Utc>) -> Result<HttpDate, Error> {
+        let secs = u64::try_from(v.timestamp()).map_err(|_| Error::new(ErrorKind::Range, None))?;
+        HttpDate::from_secs_since_epoch(secs).ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+}
+
+impl From<HttpDate> for NaiveDateTime {
+    fn from(v: HttpDate) -> NaiveDateTime {
+        DateTime::<Utc>::from(v).naive_utc()
+    }
+}
+
+impl TryFrom<NaiveDateTime> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: NaiveDateTime) -> Result<HttpDate, Error> {
+        HttpDate::try_from(v.and_utc())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+    use crate::HttpDate;
+
+    #[test]
+    fn test_chrono_conversions() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let dt = Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 37).unwrap();
+        assert_eq!(DateTime::<Utc>::from(d), dt);
+        assert_eq!(HttpDate::try_from(dt), Ok(d));
+        assert_eq!(NaiveDateTime::from(d), dt.naive_utc());
+        assert_eq!(HttpDate::try_from(dt.naive_utc()), Ok(d));
+
+        let frac = NaiveDate::from_ymd_opt(1994, 11, 6)
+            .unwrap()
+            .and_hms_milli_opt(8, 49, 37, 999)
+            .unwrap();
+        assert_eq!(HttpDate::try_from(frac), Ok(d));
+
+        let early = Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap();
+        assert!(HttpDate::try_from(early).unwrap_err().is_out_of_range());
+        let late = Utc.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap();
+        assert!(HttpDate::try_from(late).unwrap_err().is_out_of_range());
+    }
+}
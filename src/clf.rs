@@ -0,0 +1,100 @@
+use crate::date::month;
+use crate::error::Error;
+use crate::rfc3339::{from_local, num};
+use crate::HttpDate;
+
+impl HttpDate {
+    /// Parse a timestamp from a Common or Extended Log Format access log
+    /// like `[10/Oct/2000:13:55:36 -0700]`.
+    ///
+    /// The brackets are optional and the offset is applied.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::parse_clf("[10/Oct/2000:13:55:36 -0700]").unwrap();
+    /// assert_eq!(date.to_string(), "Tue, 10 Oct 2000 20:55:36 GMT");
+    /// ```
+    pub fn parse_clf(s: &str) -> Result<HttpDate, Error> {
+        let trimmed = s.trim();
+        let base = s.len() - s.trim_start().len();
+        let (base, x) = match trimmed.strip_prefix('[') {
+            Some(rest) => match rest.strip_suffix(']') {
+                Some(x) => (base + 1, x),
+                None => return Err(Error::format(s.len()).with_input(s.as_bytes(), 0)),
+            },
+            None => (base, trimmed),
+        };
+        parse(x.as_bytes()).map_err(|e| e.with_input(s.as_bytes(), base))
+    }
+}
+
+fn parse(s: &[u8]) -> Result<HttpDate, Error> {
+    // Example: `10/Oct/2000:13:55:36 -0700`
+    if s.len() != 26 {
+        return Err(Error::format(s.len().min(26)));
+    }
+    for &(at, c) in &[
+        (2, b'/'),
+        (6, b'/'),
+        (11, b':'),
+        (14, b':'),
+        (17, b':'),
+        (20, b' '),
+    ] {
+        if s[at] != c {
+            return Err(Error::format(at));
+        }
+    }
+    let mon = month(&s[3..6]).ok_or_else(|| Error::format(3))?;
+    let sign = match s[21] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return Err(Error::format(21)),
+    };
+    let offset = sign * (num(s, 22, 2)? * 3600 + num(s, 24, 2)? * 60);
+    from_local(
+        num(s, 7, 4)?,
+        i64::from(mon),
+        num(s, 0, 2)?,
+        num(s, 12, 2)?,
+        num(s, 15, 2)?,
+        num(s, 18, 2)?,
+        offset,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::HttpDate;
+
+    #[test]
+    fn test_parse_clf() {
+        let d: HttpDate = "Tue, 10 Oct 2000 20:55:36 GMT".parse().unwrap();
+        assert_eq!(HttpDate::parse_clf("[10/Oct/2000:13:55:36 -0700]"), Ok(d));
+        assert_eq!(HttpDate::parse_clf(" 10/Oct/2000:20:55:36 +0000 "), Ok(d));
+        assert_eq!(HttpDate::parse_clf("[11/Oct/2000:02:25:36 +0530]"), Ok(d));
+        assert_eq!(
+            HttpDate::parse_clf("[31/Dec/1969:23:00:00 -0100]"),
+            Ok(HttpDate::EPOCH)
+        );
+        assert!(HttpDate::parse_clf("[01/Jan/1970:00:30:00 +0100]")
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(HttpDate::parse_clf("[30/Feb/2000:13:55:36 -0700]")
+            .unwrap_err()
+            .is_invalid_date());
+        assert!(HttpDate::parse_clf("[10/Oct/2000:13:55:36 -0700")
+            .unwrap_err()
+            .is_unrecognized_format());
+        assert!(HttpDate::parse_clf("[10/oct/2000:13:55:36 -0700]").is_err());
+        assert!(HttpDate::parse_clf("[10/Oct/2000:13:55:36 0700]").is_err());
+        assert!(HttpDate::parse_clf("Tue, 10 Oct 2000 20:55:36 GMT").is_err());
+    }
+
+    #[cfg(feature = "detailed-errors")]
+    #[test]
+    fn test_parse_clf_offset() {
+        let e = HttpDate::parse_clf(" [10/Oct/2000:13:55:36 *0700]").unwrap_err();
+        assert_eq!(e.offset(), Some(23));
+    }
+}
@@ -0,0 +1,330 @@
+//! Sources of the current time.
+//!
+//! Code that takes a [`Clock`] instead of calling `HttpDate::now()` can be
+//! tested with a [`FixedClock`] or a [`MockClock`].
+
+use core::cell::Cell;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Weak};
+#[cfg(feature = "std")]
+use std::thread;
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::HttpDate;
+
+/// A source of the current time.
+pub trait Clock {
+    /// The current time, truncated to whole seconds.
+    fn now(&self) -> HttpDate;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> HttpDate {
+        (**self).now()
+    }
+}
+
+/// The system clock, see [`HttpDate::now`].
+///
+/// Only available with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> HttpDate {
+        HttpDate::now()
+    }
+}
+
+/// A clock that always returns the same time.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FixedClock(pub HttpDate);
+
+impl Clock for FixedClock {
+    fn now(&self) -> HttpDate {
+        self.0
+    }
+}
+
+/// A clock for tests that only moves when it is told to.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::{clock::{Clock, MockClock}, HttpDate};
+/// let start: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let clock = MockClock::new(start);
+/// clock.advance(Duration::from_secs(60));
+/// assert_eq!(clock.now().to_string(), "Sun, 06 Nov 1994 08:50:37 GMT");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    now: Cell<HttpDate>,
+}
+
+impl MockClock {
+    /// Create a clock starting at `now`.
+    pub fn new(now: HttpDate) -> MockClock {
+        MockClock {
+            now: Cell::new(now),
+        }
+    }
+
+    /// Set the clock to `now`, it may move backwards.
+    pub fn set(&self, now: HttpDate) {
+        self.now.set(now);
+    }
+
+    /// Move the clock forward by whole seconds, stopping at the end of
+    /// year 9999.
+    pub fn advance(&self, by: core::time::Duration) {
+        let secs = self
+            .now
+            .get()
+            .secs_since_epoch()
+            .saturating_add(by.as_secs())
+            .min(crate::date::MAX_SECS - 1);
+        self.now
+            .set(HttpDate::from_secs_since_epoch(secs).expect("clamped to valid range"));
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> HttpDate {
+        self.now.get()
+    }
+}
+
+/// A real-time clock or counter that knows the seconds since the epoch.
+///
+/// Implement this for the clock of an embedded target and wrap it in an
+/// [`RtcClock`] to use it as a [`Clock`] on `no_std` targets.
+///
+/// ```
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use httpdate::clock::{Clock, RtcClock, RtcSource};
+///
+/// /// Seconds since boot, incremented by a timer interrupt.
+/// static UPTIME: AtomicU32 = AtomicU32::new(0);
+///
+/// /// Counts from a start time set once, e.g. from NTP or a GPS fix.
+/// struct CounterClock {
+///     boot_secs: u64,
+/// }
+///
+/// impl RtcSource for CounterClock {
+///     fn unix_secs(&self) -> u64 {
+///         self.boot_secs + u64::from(UPTIME.load(Ordering::Relaxed))
+///     }
+/// }
+///
+/// let clock = RtcClock(CounterClock { boot_secs: 784111777 });
+/// UPTIME.fetch_add(60, Ordering::Relaxed);
+/// assert_eq!(clock.now().to_string(), "Sun, 06 Nov 1994 08:50:37 GMT");
+/// ```
+pub trait RtcSource {
+    /// Seconds since 1970-01-01T00:00:00Z.
+    fn unix_secs(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> RtcSource for F {
+    fn unix_secs(&self) -> u64 {
+        self()
+    }
+}
+
+/// Adapter to use an [`RtcSource`] as a [`Clock`].
+///
+/// Times after the year 9999 are clamped.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RtcClock<S>(pub S);
+
+impl<S: RtcSource> Clock for RtcClock<S> {
+    fn now(&self) -> HttpDate {
+        let secs = self.0.unix_secs().min(crate::date::MAX_SECS - 1);
+        HttpDate::from_secs_since_epoch(secs).expect("clamped to valid range")
+    }
+}
+
+/// A clock that is read once per second instead of on every request.
+///
+/// Only available with the `std` feature.
+///
+/// The time is stored in an atomic integer shared by all clones of the
+/// clock. Either call [`CoarseClock::refresh`] regularly, for example from
+/// an event loop, or create the clock with [`CoarseClock::with_ticker`] to
+/// refresh it from a background thread.
+///
+/// ```
+/// # use httpdate::CoarseClock;
+/// let clock = CoarseClock::new();
+/// let date = clock.now();
+/// clock.refresh();
+/// assert!(clock.now() >= date);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct CoarseClock {
+    secs: Arc<AtomicU64>,
+}
+
+#[cfg(feature = "std")]
+impl CoarseClock {
+    /// Create a clock set to the current time.
+    pub fn new() -> CoarseClock {
+        CoarseClock {
+            secs: Arc::new(AtomicU64::new(HttpDate::now().secs_since_epoch())),
+        }
+    }
+
+    /// Create a clock refreshed by a background thread.
+    ///
+    /// The thread wakes up shortly after every full second and exits once
+    /// all clones of the clock are dropped.
+    pub fn with_ticker() -> CoarseClock {
+        let clock = CoarseClock::new();
+        let secs = Arc::downgrade(&clock.secs);
+        thread::Builder::new()
+            .name("httpdate-clock".to_owned())
+            .spawn(move || tick(secs))
+            .expect("failed to spawn clock thread");
+        clock
+    }
+
+    /// Read the system clock and store the current time.
+    pub fn refresh(&self) {
+        self.refresh_from(&SystemClock);
+    }
+
+    /// Read `clock` and store its time.
+    pub fn refresh_from<C: Clock>(&self, clock: &C) {
+        self.secs
+            .store(clock.now().secs_since_epoch(), Ordering::Relaxed);
+    }
+
+    /// The time of the last refresh.
+    pub fn now(&self) -> HttpDate {
+        HttpDate::from_secs_since_epoch(self.secs.load(Ordering::Relaxed))
+            .expect("stored a valid date")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for CoarseClock {
+    fn now(&self) -> HttpDate {
+        CoarseClock::now(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for CoarseClock {
+    fn default() -> CoarseClock {
+        CoarseClock::new()
+    }
+}
+
+#[cfg(feature = "std")]
+fn tick(secs: Weak<AtomicU64>) {
+    loop {
+        thread::sleep(until_next_second(SystemTime::now()));
+        if !tick_once(&secs, &SystemClock) {
+            return;
+        }
+    }
+}
+
+/// Store the time of `clock`, returns `false` once the clock is dropped.
+#[cfg(feature = "std")]
+fn tick_once<C: Clock>(secs: &Weak<AtomicU64>, clock: &C) -> bool {
+    match secs.upgrade() {
+        Some(secs) => {
+            secs.store(clock.now().secs_since_epoch(), Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Time from `now` to the start of the next second.
+#[cfg(feature = "std")]
+fn until_next_second(now: SystemTime) -> Duration {
+    let subsec = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    Duration::from_nanos(u64::from(1_000_000_000 - subsec))
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        tick_once, until_next_second, Clock, CoarseClock, FixedClock, MockClock, RtcClock,
+        RtcSource, SystemClock,
+    };
+    use crate::HttpDate;
+
+    fn elapsed<C: Clock>(clock: C, start: HttpDate) -> u64 {
+        clock.now().secs_since_epoch() - start.secs_since_epoch()
+    }
+
+    #[test]
+    fn test_clocks() {
+        let start: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert_eq!(elapsed(FixedClock(start), start), 0);
+        let mock = MockClock::new(start);
+        mock.advance(Duration::from_millis(1500));
+        assert_eq!(elapsed(&mock, start), 1);
+        mock.advance(Duration::from_secs(u64::MAX));
+        assert_eq!(mock.now().to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+        mock.set(start);
+        assert_eq!(elapsed(&mock, start), 0);
+        assert!(SystemClock.now() > start);
+    }
+
+    #[test]
+    fn test_rtc_clock() {
+        struct Rtc(u64);
+
+        impl RtcSource for Rtc {
+            fn unix_secs(&self) -> u64 {
+                self.0
+            }
+        }
+
+        assert_eq!(
+            RtcClock(Rtc(0)).now(),
+            HttpDate::from(std::time::UNIX_EPOCH)
+        );
+        assert_eq!(
+            RtcClock(Rtc(u64::MAX)).now().to_string(),
+            "Fri, 31 Dec 9999 23:59:59 GMT"
+        );
+    }
+
+    #[test]
+    fn test_ticker() {
+        let start: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let mock = MockClock::new(start);
+        let clock = CoarseClock::new();
+        clock.refresh_from(&FixedClock(start));
+        assert_eq!(clock.now(), start);
+
+        let secs = Arc::downgrade(&clock.secs);
+        mock.advance(Duration::from_secs(1));
+        assert!(tick_once(&secs, &mock));
+        assert_eq!(elapsed(clock.clone(), start), 1);
+        drop(clock);
+        assert!(!tick_once(&secs, &mock));
+
+        let now = UNIX_EPOCH + Duration::from_millis(784111777250);
+        assert_eq!(until_next_second(now), Duration::from_millis(750));
+        assert_eq!(until_next_second(UNIX_EPOCH), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,286 @@
+//! Evaluation of conditional request header fields from RFC 9110 section 13.
+//!
+//! All comparisons are done with the one second resolution of HTTP dates.
+//! Convert file modification times to `HttpDate` first to truncate them.
+
+use crate::HttpDate;
+
+/// Evaluate `If-Modified-Since`, see RFC 9110 section 13.1.3.
+///
+/// Returns `true` if the resource was not modified after the date in
+/// the header field, a `GET` or `HEAD` request should then be answered
+/// with `304 Not Modified`.
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use httpdate::{conditional::not_modified_since, HttpDate};
+/// let mtime = UNIX_EPOCH + Duration::from_millis(784111777_250);
+/// let if_modified_since: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert!(not_modified_since(mtime.into(), if_modified_since));
+/// ```
+pub fn not_modified_since(last_modified: HttpDate, if_modified_since: HttpDate) -> bool {
+    last_modified <= if_modified_since
+}
+
+/// Evaluate `If-Unmodified-Since`, see RFC 9110 section 13.1.4.
+///
+/// Takes the raw field value. Returns `true` if the precondition holds
+/// and the request may be performed, that is the resource was not
+/// modified after the date in the header field. Otherwise the server
+/// should answer with `412 Precondition Failed`.
+///
+/// A field value that is not a valid HTTP date is ignored as the
+/// specification requires, the precondition then holds.
+///
+/// ```
+/// # use httpdate::{conditional::unmodified_since, HttpDate};
+/// let last_modified: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert!(unmodified_since(last_modified, "Sun, 06 Nov 1994 08:49:37 GMT"));
+/// assert!(!unmodified_since(last_modified, "Sun, 06 Nov 1994 08:49:36 GMT"));
+/// assert!(unmodified_since(last_modified, "yesterday"));
+/// ```
+pub fn unmodified_since<V: AsRef<[u8]>>(last_modified: HttpDate, if_unmodified_since: V) -> bool {
+    match HttpDate::parse_bytes(if_unmodified_since.as_ref()) {
+        Ok(date) => last_modified <= date,
+        Err(_) => true,
+    }
+}
+
+/// Strength of a `Last-Modified` validator.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ValidatorStrength {
+    /// The representation can't have changed within the same second.
+    Strong,
+    /// The representation may have changed again within the same second.
+    Weak,
+}
+
+/// Classify a `Last-Modified` date, see RFC 9110 section 8.8.2.2.
+///
+/// The date is a strong validator if it is at least one second older than
+/// `date`, the time the response was generated. Otherwise the
+/// representation might have been changed twice in the same second.
+///
+/// ```
+/// # use httpdate::{conditional::{validator_strength, ValidatorStrength}, HttpDate};
+/// let last_modified: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let date: HttpDate = "Sun, 06 Nov 1994 08:49:38 GMT".parse().unwrap();
+/// assert_eq!(validator_strength(last_modified, date), ValidatorStrength::Strong);
+/// assert_eq!(validator_strength(last_modified, last_modified), ValidatorStrength::Weak);
+/// ```
+pub fn validator_strength(last_modified: HttpDate, date: HttpDate) -> ValidatorStrength {
+    if last_modified < date {
+        ValidatorStrength::Strong
+    } else {
+        ValidatorStrength::Weak
+    }
+}
+
+/// Replace a `Last-Modified` date in the future with the message `Date`.
+///
+/// An origin server must not send a `Last-Modified` date later than the
+/// `Date` of the message, see RFC 9110 section 8.8.2.1. This happens when
+/// a file was written on a machine with a clock ahead of the server.
+///
+/// ```
+/// # use httpdate::{conditional::clamp_last_modified, HttpDate};
+/// let mtime: HttpDate = "Sun, 06 Nov 1994 08:49:38 GMT".parse().unwrap();
+/// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert_eq!(clamp_last_modified(mtime, date), date);
+/// ```
+pub fn clamp_last_modified(last_modified: HttpDate, date: HttpDate) -> HttpDate {
+    last_modified.min(date)
+}
+
+/// Evaluate a date in `If-Range`, see RFC 9110 section 13.1.5.
+///
+/// Returns `true` if the date exactly matches the `Last-Modified` date of
+/// the representation, the range request should then be served. Only
+/// use dates that are strong validators, see [`validator_strength`]. Unlike
+/// `If-Modified-Since` an older or newer date does not match, otherwise a
+/// client could combine parts of different representations.
+///
+/// ```
+/// # use httpdate::{conditional::if_range_matches, HttpDate};
+/// let last_modified: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let later: HttpDate = "Sun, 06 Nov 1994 08:49:38 GMT".parse().unwrap();
+/// assert!(if_range_matches(last_modified, last_modified));
+/// assert!(!if_range_matches(last_modified, later));
+/// ```
+pub fn if_range_matches(last_modified: HttpDate, if_range_date: HttpDate) -> bool {
+    last_modified == if_range_date
+}
+
+/// Outcome of evaluating the preconditions of a request.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Decision {
+    /// Perform the request, serving a requested range if any.
+    Proceed,
+    /// Perform the request but ignore the `Range` header field and send
+    /// the full representation.
+    IgnoreRange,
+    /// Answer with `304 Not Modified`.
+    NotModified,
+    /// Answer with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+/// The date based preconditions of a request.
+///
+/// Set the dates for header fields that are present and contain a valid
+/// date, invalid dates must be ignored. Only set `if_modified_since` for
+/// `GET` and `HEAD` requests and `if_range` for `GET` requests with a
+/// `Range` header field.
+///
+/// ```
+/// # use httpdate::{conditional::{ConditionalRequest, Decision}, HttpDate};
+/// let last_modified: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let date = HttpDate::now();
+/// let request = ConditionalRequest {
+///     if_modified_since: Some(last_modified),
+///     ..ConditionalRequest::default()
+/// };
+/// assert_eq!(request.evaluate(last_modified, date), Decision::NotModified);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ConditionalRequest {
+    /// `If-Modified-Since` header field.
+    pub if_modified_since: Option<HttpDate>,
+    /// `If-Unmodified-Since` header field.
+    pub if_unmodified_since: Option<HttpDate>,
+    /// Date in the `If-Range` header field.
+    pub if_range: Option<HttpDate>,
+    /// An `If-Match` header field is present, `If-Unmodified-Since` is then ignored.
+    pub has_if_match: bool,
+    /// An `If-None-Match` header field is present, `If-Modified-Since` is then ignored.
+    pub has_if_none_match: bool,
+}
+
+impl ConditionalRequest {
+    /// Evaluate the preconditions in the order of RFC 9110 section 13.2.2.
+    ///
+    /// `last_modified` is the `Last-Modified` date of the selected
+    /// representation and `date` the `Date` of the response. Entity-tag
+    /// preconditions must be evaluated by the caller, their presence only
+    /// disables the date based fallbacks.
+    pub fn evaluate(&self, last_modified: HttpDate, date: HttpDate) -> Decision {
+        if !self.has_if_match {
+            if let Some(if_unmodified_since) = self.if_unmodified_since {
+                if last_modified > if_unmodified_since {
+                    return Decision::PreconditionFailed;
+                }
+            }
+        }
+        if !self.has_if_none_match {
+            if let Some(if_modified_since) = self.if_modified_since {
+                if not_modified_since(last_modified, if_modified_since) {
+                    return Decision::NotModified;
+                }
+            }
+        }
+        if let Some(if_range) = self.if_range {
+            let strong = validator_strength(last_modified, date) == ValidatorStrength::Strong;
+            if !strong || !if_range_matches(last_modified, if_range) {
+                return Decision::IgnoreRange;
+            }
+        }
+        Decision::Proceed
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        clamp_last_modified, if_range_matches, not_modified_since, unmodified_since,
+        validator_strength, ConditionalRequest, Decision, ValidatorStrength,
+    };
+    use crate::HttpDate;
+
+    fn d(secs: u64) -> HttpDate {
+        HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn test_not_modified_since() {
+        assert!(not_modified_since(d(100), d(100)));
+        assert!(not_modified_since(d(99), d(100)));
+        assert!(!not_modified_since(d(101), d(100)));
+        let mtime = UNIX_EPOCH + Duration::from_millis(100_999);
+        assert!(not_modified_since(mtime.into(), d(100)));
+    }
+
+    #[test]
+    fn test_unmodified_since() {
+        assert!(unmodified_since(
+            d(784111777),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        ));
+        assert!(unmodified_since(d(784111776), b"Sun Nov  6 08:49:37 1994"));
+        assert!(!unmodified_since(
+            d(784111778),
+            "Sunday, 06-Nov-94 08:49:37 GMT"
+        ));
+        assert!(unmodified_since(
+            d(784111778),
+            "Sun, 06 Nov 1994 08:49:37 UTC"
+        ));
+        assert!(unmodified_since(d(784111778), ""));
+    }
+
+    #[test]
+    fn test_if_range_matches() {
+        assert!(if_range_matches(d(100), d(100)));
+        assert!(!if_range_matches(d(99), d(100)));
+        assert!(!if_range_matches(d(101), d(100)));
+    }
+
+    #[test]
+    fn test_validator_strength() {
+        assert_eq!(validator_strength(d(99), d(100)), ValidatorStrength::Strong);
+        assert_eq!(validator_strength(d(100), d(100)), ValidatorStrength::Weak);
+        assert_eq!(validator_strength(d(101), d(100)), ValidatorStrength::Weak);
+    }
+
+    #[test]
+    fn test_clamp_last_modified() {
+        assert_eq!(clamp_last_modified(d(99), d(100)), d(99));
+        assert_eq!(clamp_last_modified(d(101), d(100)), d(100));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let lm = d(100);
+        let date = d(200);
+        let req = ConditionalRequest::default();
+        assert_eq!(req.evaluate(lm, date), Decision::Proceed);
+
+        let req = ConditionalRequest {
+            if_unmodified_since: Some(d(99)),
+            if_modified_since: Some(d(100)),
+            ..ConditionalRequest::default()
+        };
+        assert_eq!(req.evaluate(lm, date), Decision::PreconditionFailed);
+        let req = ConditionalRequest {
+            has_if_match: true,
+            ..req
+        };
+        assert_eq!(req.evaluate(lm, date), Decision::NotModified);
+        let req = ConditionalRequest {
+            has_if_none_match: true,
+            ..req
+        };
+        assert_eq!(req.evaluate(lm, date), Decision::Proceed);
+
+        let req = ConditionalRequest {
+            if_modified_since: Some(d(99)),
+            if_range: Some(d(100)),
+            ..ConditionalRequest::default()
+        };
+        assert_eq!(req.evaluate(lm, date), Decision::Proceed);
+        assert_eq!(req.evaluate(lm, lm), Decision::IgnoreRange);
+        assert_eq!(req.evaluate(d(101), date), Decision::IgnoreRange);
+    }
+}
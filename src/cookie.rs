@@ -0,0 +1,97 @@
+//! Interoperability with the `cookie` crate, enabled with the `cookie` feature.
+//!
+//! `HttpDate` converts from and to `cookie::Expiration` and the
+//! `time::OffsetDateTime` the cookie crate uses. Converting to `HttpDate`
+//! truncates sub-second precision and fails for session cookies and
+//! dates before 1970 or after 9999.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+use ::cookie::time::OffsetDateTime;
+use ::cookie::Expiration;
+
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+impl From<HttpDate> for OffsetDateTime {
+    fn from(v: HttpDate) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(v.secs_since_epoch() as i64)
+            .expect("all HTTP dates are valid time dates")
+    }
+}
+
+impl TryFrom<OffsetDateTime> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: OffsetDateTime) -> Result<HttpDate, Error> {
+        u64::try_from(v.unix_timestamp())
+            .ok()
+            .and_then(HttpDate::from_secs_since_epoch)
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+}
+
+impl From<HttpDate> for Expiration {
+    fn from(v: HttpDate) -> Expiration {
+        Expiration::DateTime(v.into())
+    }
+}
+
+impl TryFrom<Expiration> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: Expiration) -> Result<HttpDate, Error> {
+        match v {
+            Expiration::DateTime(t) => HttpDate::try_from(t),
+            Expiration::Session => Err(Error::new(ErrorKind::Range, None)),
+        }
+    }
+}
+
+/// Formats the `Expires` attribute of a `Set-Cookie` header field:
+/// `Expires=Sun, 06 Nov 1994 08:49:37 GMT`.
+#[derive(Copy, Clone, Debug)]
+pub struct ExpiresAttribute(pub HttpDate);
+
+impl Display for ExpiresAttribute {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Expires={}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::cookie::time::{Duration, OffsetDateTime, UtcOffset};
+    use ::cookie::{Cookie, Expiration};
+
+    use super::ExpiresAttribute;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_expiration() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let e = Expiration::from(d);
+        let t = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+        assert_eq!(e.datetime(), Some(t));
+        assert_eq!(HttpDate::try_from(e), Ok(d));
+        let offset = UtcOffset::from_hms(1, 0, 0).unwrap();
+        let t = (t + Duration::milliseconds(500)).to_offset(offset);
+        assert_eq!(HttpDate::try_from(Expiration::DateTime(t)), Ok(d));
+        assert!(HttpDate::try_from(Expiration::Session).is_err());
+        let early = OffsetDateTime::from_unix_timestamp(-1).unwrap();
+        assert!(HttpDate::try_from(early).is_err());
+
+        let cookie = Cookie::build(("id", "a3fWa")).expires(d).build();
+        assert_eq!(
+            cookie.to_string(),
+            "id=a3fWa; Expires=Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+        assert_eq!(
+            ExpiresAttribute(d).to_string(),
+            "Expires=Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+    }
+}
@@ -1,9 +1,16 @@
-use std::cmp;
-use std::fmt::{self, Display, Formatter};
-use std::str::FromStr;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::fs::Metadata;
+#[cfg(feature = "std")]
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::Error;
+use crate::calendar::{is_leap_year, month_len};
+use crate::error::{Error, ErrorKind};
+
+/// Seconds from the epoch to 10000-01-01T00:00:00Z.
+pub(crate) const MAX_SECS: u64 = 253402300800;
 
 /// HTTP timestamp type.
 ///
@@ -11,50 +18,197 @@ use crate::Error;
 /// Format using the `Display` trait.
 /// Convert timestamp into/from `SytemTime` to use.
 /// Supports comparsion and sorting.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+///
+/// Only the seconds since the epoch are stored. The weekday is computed
+/// from the date whenever it is needed and can't get out of sync.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct HttpDate {
+    /// Seconds since the epoch, less than `MAX_SECS`.
+    secs: u64,
+}
+
+/// Length of a date formatted as IMF-fixdate.
+pub const IMF_FIXDATE_LEN: usize = 29;
+
+/// Deviations from the standard formats the parser accepts on request.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct Leniency {
+    /// Move days after the end of the month, like 31 April, to the last day.
+    pub(crate) clamp_day: bool,
+    /// Accept an offset after `GMT`, like `GMT+0100`, and apply it.
+    pub(crate) zone_offset: bool,
+    /// Accept an IMF-fixdate without the comma after the weekday.
+    pub(crate) missing_comma: bool,
+    /// Accept hours, minutes and seconds with a single digit.
+    pub(crate) short_time: bool,
+    /// Accept a day of the month with a single digit.
+    pub(crate) short_day: bool,
+    /// Accept a two digit month number in place of the month name.
+    pub(crate) numeric_month: bool,
+}
+
+impl Leniency {
+    /// Parse IMF-fixdates token by token instead of at fixed offsets.
+    fn tokenize(&self) -> bool {
+        self.missing_comma || self.short_time || self.short_day || self.numeric_month
+    }
+}
+
+/// The calendar fields of a date.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Fields {
     /// 0...59
-    sec: u8,
+    pub(crate) sec: u8,
     /// 0...59
-    min: u8,
+    pub(crate) min: u8,
     /// 0...23
-    hour: u8,
+    pub(crate) hour: u8,
     /// 1...31
-    day: u8,
+    pub(crate) day: u8,
     /// 1...12
-    mon: u8,
+    pub(crate) mon: u8,
     /// 1970...9999
-    year: u16,
+    pub(crate) year: u16,
     /// 1...7
-    wday: u8,
+    pub(crate) wday: u8,
 }
 
 impl HttpDate {
-    fn is_valid(&self) -> bool {
-        self.sec < 60
-            && self.min < 60
-            && self.hour < 24
-            && self.day > 0
-            && self.day < 32
-            && self.mon > 0
-            && self.mon <= 12
-            && self.year >= 1970
-            && self.year <= 9999
-            && &HttpDate::from(SystemTime::from(*self)) == self
+    /// The current time, truncated to whole seconds.
+    ///
+    /// The standard library can't read the clock on `wasm32-unknown-unknown`,
+    /// enable the `wasm` feature to use the browser clock there. On other
+    /// platforms without a working clock implement [`Clock`](crate::clock::Clock)
+    /// and pass it to [`DateHeaderCache::with_clock`](crate::DateHeaderCache::with_clock).
+    ///
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn now() -> HttpDate {
+        #[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+        {
+            HttpDate::from(web_time::SystemTime::now())
+        }
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
+        {
+            HttpDate::from(SystemTime::now())
+        }
     }
-}
 
-impl From<SystemTime> for HttpDate {
-    fn from(v: SystemTime) -> HttpDate {
-        let dur = v
-            .duration_since(UNIX_EPOCH)
-            .expect("all times should be after the epoch");
-        let secs_since_epoch = dur.as_secs();
+    /// Truncate a system time to whole seconds.
+    ///
+    /// Returns an out of range error for times before 1970 or after 9999
+    /// instead of panicking like the `From` conversion.
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// # use httpdate::HttpDate;
+    /// assert!(HttpDate::try_from_system_time(UNIX_EPOCH).is_ok());
+    /// let before = UNIX_EPOCH - Duration::from_secs(1);
+    /// assert!(HttpDate::try_from_system_time(before).unwrap_err().is_out_of_range());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_from_system_time(v: SystemTime) -> Result<HttpDate, Error> {
+        v.duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|dur| HttpDate::from_secs_since_epoch(dur.as_secs()))
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
 
-        if secs_since_epoch >= 253402300800 {
-            // year 9999
-            panic!("date must be before year 9999");
+    /// Truncate a system time to whole seconds, saturating at 1970 and 9999.
+    ///
+    /// Unlike the `From` conversion this never panics, times outside the
+    /// range of HTTP dates become [`HttpDate::MIN`] or [`HttpDate::MAX`].
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// # use httpdate::HttpDate;
+    /// let far = UNIX_EPOCH + Duration::from_secs(1 << 40);
+    /// assert_eq!(HttpDate::from_system_time_saturating(far), HttpDate::MAX);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_system_time_saturating(v: SystemTime) -> HttpDate {
+        match v.duration_since(UNIX_EPOCH) {
+            Ok(dur) => HttpDate::from_secs_since_epoch(dur.as_secs()).unwrap_or(HttpDate::MAX),
+            Err(_) => HttpDate::MIN,
         }
+    }
+
+    /// The modification time of a file, truncated to whole seconds.
+    ///
+    /// Returns an unavailable error if the platform or file system does not
+    /// record modification times and an out of range error for times before
+    /// 1970 or after 9999.
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let metadata = std::fs::metadata("Cargo.toml")?;
+    /// let last_modified = HttpDate::from_metadata(&metadata)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_metadata(metadata: &Metadata) -> Result<HttpDate, Error> {
+        let modified = metadata
+            .modified()
+            .map_err(|_| Error::new(ErrorKind::Unavailable, None))?;
+        HttpDate::try_from_system_time(modified)
+    }
+
+    /// Whole seconds from `now` until this date, for `Retry-After` or
+    /// `max-age` values.
+    ///
+    /// Returns `None` if the date has already passed. Partial seconds are
+    /// rounded up so clients never come back too early.
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// # use std::time::{Duration, SystemTime};
+    /// # use httpdate::HttpDate;
+    /// let now = SystemTime::now();
+    /// let date = HttpDate::from(now + Duration::from_secs(120));
+    /// assert!(matches!(date.seconds_until(now), Some(119..=120)));
+    /// assert_eq!(date.seconds_until(now + Duration::from_secs(3600)), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn seconds_until(&self, now: SystemTime) -> Option<u64> {
+        let dur = SystemTime::from(*self).duration_since(now).ok()?;
+        Some(dur.as_secs() + u64::from(dur.subsec_nanos() > 0))
+    }
+
+    /// A date in the past for `Expires` headers that mark a response as
+    /// already expired: `Thu, 01 Jan 1970 00:00:00 GMT`.
+    ///
+    /// See also [`fmt_expired`](crate::fmt_expired).
+    pub const EXPIRED: HttpDate = HttpDate::EPOCH;
+
+    /// 1970-01-01T00:00:00Z, the earliest date.
+    pub(crate) const EPOCH: HttpDate = HttpDate { secs: 0 };
+
+    /// The earliest date: `Thu, 01 Jan 1970 00:00:00 GMT`.
+    pub const MIN: HttpDate = HttpDate::EPOCH;
+
+    /// The latest date: `Fri, 31 Dec 9999 23:59:59 GMT`.
+    pub const MAX: HttpDate = HttpDate { secs: MAX_SECS - 1 };
+
+    /// Create a date from seconds since the epoch, if it is before year 10000.
+    pub(crate) fn from_secs_since_epoch(secs_since_epoch: u64) -> Option<HttpDate> {
+        if secs_since_epoch >= MAX_SECS {
+            return None;
+        }
+        Some(HttpDate {
+            secs: secs_since_epoch,
+        })
+    }
+
+    pub(crate) fn secs_since_epoch(&self) -> u64 {
+        self.secs
+    }
+
+    /// Split the date into its calendar fields.
+    pub(crate) fn fields(&self) -> Fields {
+        let secs_since_epoch = self.secs;
 
         /* 2000-03-01 (mod 400 year, immediately after feb29 */
         const LEAPOCH: i64 = 11017;
@@ -115,7 +269,7 @@ impl From<SystemTime> for HttpDate {
             wday += 7
         };
 
-        HttpDate {
+        Fields {
             sec: (secs_of_day % 60) as u8,
             min: ((secs_of_day % 3600) / 60) as u8,
             hour: (secs_of_day / 3600) as u8,
@@ -125,13 +279,182 @@ impl From<SystemTime> for HttpDate {
             wday: wday as u8,
         }
     }
+
+    /// Format the date as IMF-fixdate into a buffer without allocating.
+    ///
+    /// Returns the filled buffer, the bytes are always ASCII.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// let mut buf = [0; httpdate::IMF_FIXDATE_LEN];
+    /// assert_eq!(date.format_into(&mut buf), b"Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    pub fn format_into<'a>(&self, buf: &'a mut [u8; IMF_FIXDATE_LEN]) -> &'a [u8; IMF_FIXDATE_LEN] {
+        let f = self.fields();
+        let wday = WEEKDAY_PREFIXES[usize::from(f.wday - 1)].as_bytes();
+        let mon = MONTH_INFIXES[usize::from(f.mon - 1)].as_bytes();
+
+        *buf = *b"   , 00     0000 00:00:00 GMT";
+        buf[0] = wday[0];
+        buf[1] = wday[1];
+        buf[2] = wday[2];
+        buf[5] = b'0' + (f.day / 10);
+        buf[6] = b'0' + (f.day % 10);
+        buf[8] = mon[1];
+        buf[9] = mon[2];
+        buf[10] = mon[3];
+        buf[12] = b'0' + (f.year / 1000) as u8;
+        buf[13] = b'0' + (f.year / 100 % 10) as u8;
+        buf[14] = b'0' + (f.year / 10 % 10) as u8;
+        buf[15] = b'0' + (f.year % 10) as u8;
+        buf[17] = b'0' + (f.hour / 10);
+        buf[18] = b'0' + (f.hour % 10);
+        buf[20] = b'0' + (f.min / 10);
+        buf[21] = b'0' + (f.min % 10);
+        buf[23] = b'0' + (f.sec / 10);
+        buf[24] = b'0' + (f.sec % 10);
+        buf
+    }
+
+    /// Display the date in the obsolete RFC 850 format with a two digit
+    /// year, like `Sunday, 06-Nov-94 08:49:37 GMT`, to test old clients.
+    ///
+    /// Two digit years are read as 1970 to 2069, the pivot used by the
+    /// parser. Returns `None` for other years as they would be read back
+    /// as a different date.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// assert_eq!(date.rfc850().unwrap().to_string(), "Sunday, 06-Nov-94 08:49:37 GMT");
+    /// assert!(HttpDate::MAX.rfc850().is_none());
+    /// ```
+    pub fn rfc850(&self) -> Option<impl Display> {
+        let fields = self.fields();
+        if fields.year < 2070 {
+            Some(Rfc850(fields))
+        } else {
+            None
+        }
+    }
+
+    /// Parse a header field value sliced from a network or C buffer.
+    ///
+    /// Surrounding whitespace including a trailing CRLF is ignored, and so
+    /// are NUL bytes padding the end of a fixed size buffer.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::parse_raw(b"Sun, 06 Nov 1994 08:49:37 GMT\r\n").unwrap();
+    /// assert_eq!(HttpDate::parse_raw(b"Sun, 06 Nov 1994 08:49:37 GMT\0\0\0"), Ok(date));
+    /// ```
+    pub fn parse_raw(s: &[u8]) -> Result<HttpDate, Error> {
+        let nuls = s.iter().rev().take_while(|&&b| b == 0).count();
+        HttpDate::parse_bytes(&s[..s.len() - nuls])
+    }
+
+    /// Parse a date from bytes that may be surrounded by whitespace.
+    pub(crate) fn parse_bytes(s: &[u8]) -> Result<HttpDate, Error> {
+        HttpDate::parse_lenient(s, Leniency::default())
+    }
+
+    /// Like `parse_bytes` but also accept the deviations allowed by `leniency`.
+    pub(crate) fn parse_lenient(s: &[u8], leniency: Leniency) -> Result<HttpDate, Error> {
+        let parse = |x: &[u8]| match Format::detect(x) {
+            Format::ImfFixdate if leniency.tokenize() => parse_imf_fixdate_lenient(x, leniency),
+            Format::Asctime if leniency.missing_comma && x.ends_with(b" GMT") => {
+                parse_imf_fixdate_lenient(x, leniency)
+            }
+            format => format.parse(x),
+        };
+        if leniency.zone_offset {
+            if let Some((x, offset)) = split_zone_offset(s) {
+                let date = HttpDate::parse_with(x, parse, leniency)?;
+                return (date.secs as i64)
+                    .checked_sub(offset)
+                    .and_then(|secs| u64::try_from(secs).ok())
+                    .and_then(HttpDate::from_secs_since_epoch)
+                    .ok_or_else(|| Error::new(ErrorKind::Range, None).with_input(s, 0));
+            }
+        }
+        HttpDate::parse_with(s, parse, leniency)
+    }
+
+    /// Parse a date that fills the whole input, without trimming it.
+    pub(crate) fn parse_exact(s: &[u8]) -> Option<HttpDate> {
+        Format::detect(s).parse(s).and_then(|f| f.validate()).ok()
+    }
+
+    /// Check if `parse_bytes` would succeed without building an error.
+    pub(crate) fn is_valid(s: &[u8]) -> bool {
+        s.is_ascii() && HttpDate::parse_exact(trim(s).1).is_some()
+    }
+
+    /// Like `parse_bytes` but only accept the IMF-fixdate format.
+    pub(crate) fn parse_imf_fixdate_bytes(s: &[u8]) -> Result<HttpDate, Error> {
+        HttpDate::parse_with(s, parse_imf_fixdate, Leniency::default())
+    }
+
+    fn parse_with<F>(s: &[u8], parse: F, leniency: Leniency) -> Result<HttpDate, Error>
+    where
+        F: FnOnce(&[u8]) -> Result<Fields, Error>,
+    {
+        if let Some(pos) = s.iter().position(|b| !b.is_ascii()) {
+            return Err(Error::format(pos).with_input(s, 0));
+        }
+        let (base, x) = trim(s);
+        parse(x)
+            .and_then(|fields| fields.validate_lenient(leniency))
+            .map_err(|e| e.with_input(s, base))
+    }
 }
 
+#[cfg(feature = "std")]
+impl From<SystemTime> for HttpDate {
+    /// Truncate a system time to whole seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time is before 1970 or after 9999, use
+    /// [`HttpDate::try_from_system_time`] to handle these times.
+    fn from(v: SystemTime) -> HttpDate {
+        let dur = v
+            .duration_since(UNIX_EPOCH)
+            .expect("all times should be after the epoch");
+        let secs_since_epoch = dur.as_secs();
+        HttpDate::from_secs_since_epoch(secs_since_epoch)
+            .unwrap_or_else(|| panic!("date must be before year 9999"))
+    }
+}
+
+#[cfg(feature = "std")]
 impl From<HttpDate> for SystemTime {
     fn from(v: HttpDate) -> SystemTime {
-        let leap_years =
-            ((v.year - 1) - 1968) / 4 - ((v.year - 1) - 1900) / 100 + ((v.year - 1) - 1600) / 400;
-        let mut ydays = match v.mon {
+        UNIX_EPOCH + Duration::from_secs(v.secs_since_epoch())
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<HttpDate, Error> {
+        HttpDate::parse_bytes(s.as_bytes())
+    }
+}
+
+impl Display for HttpDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fields().write_to(f)
+    }
+}
+
+impl Fields {
+    /// Seconds since the epoch, the fields must be in range.
+    fn secs_since_epoch(&self) -> u64 {
+        let leap_years = ((self.year - 1) - 1968) / 4 - ((self.year - 1) - 1900) / 100
+            + ((self.year - 1) - 1600) / 400;
+        let mut ydays = match self.mon {
             1 => 0,
             2 => 31,
             3 => 59,
@@ -145,276 +468,569 @@ impl From<HttpDate> for SystemTime {
             11 => 304,
             12 => 334,
             _ => unreachable!(),
-        } + v.day as u64
+        } + self.day as u64
             - 1;
-        if is_leap_year(v.year) && v.mon > 2 {
+        if is_leap_year(self.year) && self.mon > 2 {
             ydays += 1;
         }
-        let days = (v.year as u64 - 1970) * 365 + leap_years as u64 + ydays;
-        UNIX_EPOCH
-            + Duration::from_secs(
-                v.sec as u64 + v.min as u64 * 60 + v.hour as u64 * 3600 + days * 86400,
-            )
+        let days = (self.year as u64 - 1970) * 365 + leap_years as u64 + ydays;
+        self.sec as u64 + self.min as u64 * 60 + self.hour as u64 * 3600 + days * 86400
     }
-}
 
-impl FromStr for HttpDate {
-    type Err = Error;
+    /// Check that the fields are in range and describe a real date.
+    fn validate(&self) -> Result<HttpDate, Error> {
+        self.check_range()?;
+        let date = HttpDate {
+            secs: self.secs_since_epoch(),
+        };
+        if date.fields() != *self {
+            return Err(Error::new(ErrorKind::Calendar, None));
+        }
+        Ok(date)
+    }
 
-    fn from_str(s: &str) -> Result<HttpDate, Error> {
-        if !s.is_ascii() {
-            return Err(Error(()));
+    /// Like `validate` but also accept the deviations allowed by `leniency`.
+    fn validate_lenient(&self, leniency: Leniency) -> Result<HttpDate, Error> {
+        match self.validate() {
+            Err(e)
+                if leniency.clamp_day
+                    && e.is_invalid_date()
+                    && self.day > month_len(self.year, self.mon) =>
+            {
+                Fields {
+                    day: month_len(self.year, self.mon),
+                    ..*self
+                }
+                .validate_any_weekday()
+            }
+            result => result,
         }
-        let x = s.trim().as_bytes();
-        let date = parse_imf_fixdate(x)
-            .or_else(|_| parse_rfc850_date(x))
-            .or_else(|_| parse_asctime(x))?;
-        if !date.is_valid() {
-            return Err(Error(()));
+    }
+
+    /// Like `validate` but compute the weekday instead of checking it.
+    pub(crate) fn validate_any_weekday(mut self) -> Result<HttpDate, Error> {
+        self.check_range()?;
+        self.wday = HttpDate {
+            secs: self.secs_since_epoch(),
         }
-        Ok(date)
+        .fields()
+        .wday;
+        self.validate()
+    }
+
+    /// Check that the fields other than the weekday are in range.
+    fn check_range(&self) -> Result<(), Error> {
+        if !(self.sec < 60
+            && self.min < 60
+            && self.hour < 24
+            && self.day > 0
+            && self.day < 32
+            && self.mon > 0
+            && self.mon <= 12
+            && self.year >= 1970
+            && self.year <= 9999)
+        {
+            return Err(Error::new(ErrorKind::Range, None));
+        }
+        Ok(())
+    }
+
+    /// Write the fields as IMF-fixdate.
+    ///
+    /// The output is assembled from static strings so there is nothing
+    /// to check for valid UTF-8.
+    fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        w.write_str(WEEKDAY_PREFIXES[usize::from(self.wday - 1)])?;
+        w.write_str(two_digits(self.day))?;
+        w.write_str(MONTH_INFIXES[usize::from(self.mon - 1)])?;
+        w.write_str(two_digits((self.year / 100) as u8))?;
+        w.write_str(two_digits((self.year % 100) as u8))?;
+        w.write_str(" ")?;
+        w.write_str(two_digits(self.hour))?;
+        w.write_str(":")?;
+        w.write_str(two_digits(self.min))?;
+        w.write_str(":")?;
+        w.write_str(two_digits(self.sec))?;
+        w.write_str(" GMT")
     }
 }
 
-impl Display for HttpDate {
+/// Formats a date in the RFC 850 format, the year must be before 2070.
+struct Rfc850(Fields);
+
+impl Display for Rfc850 {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let wday = match self.wday {
-            1 => b"Mon",
-            2 => b"Tue",
-            3 => b"Wed",
-            4 => b"Thu",
-            5 => b"Fri",
-            6 => b"Sat",
-            7 => b"Sun",
-            _ => unreachable!(),
-        };
+        let d = &self.0;
+        f.write_str(WEEKDAY_NAMES[usize::from(d.wday - 1)])?;
+        f.write_str(", ")?;
+        f.write_str(two_digits(d.day))?;
+        f.write_str("-")?;
+        f.write_str(&MONTH_INFIXES[usize::from(d.mon - 1)][1..4])?;
+        f.write_str("-")?;
+        f.write_str(two_digits((d.year % 100) as u8))?;
+        f.write_str(" ")?;
+        f.write_str(two_digits(d.hour))?;
+        f.write_str(":")?;
+        f.write_str(two_digits(d.min))?;
+        f.write_str(":")?;
+        f.write_str(two_digits(d.sec))?;
+        f.write_str(" GMT")
+    }
+}
 
-        let mon = match self.mon {
-            1 => b"Jan",
-            2 => b"Feb",
-            3 => b"Mar",
-            4 => b"Apr",
-            5 => b"May",
-            6 => b"Jun",
-            7 => b"Jul",
-            8 => b"Aug",
-            9 => b"Sep",
-            10 => b"Oct",
-            11 => b"Nov",
-            12 => b"Dec",
-            _ => unreachable!(),
-        };
+const WEEKDAY_PREFIXES: [&str; 7] = [
+    "Mon, ", "Tue, ", "Wed, ", "Thu, ", "Fri, ", "Sat, ", "Sun, ",
+];
 
-        let mut buf: [u8; 29] = *b"   , 00     0000 00:00:00 GMT";
-        buf[0] = wday[0];
-        buf[1] = wday[1];
-        buf[2] = wday[2];
-        buf[5] = b'0' + (self.day / 10);
-        buf[6] = b'0' + (self.day % 10);
-        buf[8] = mon[0];
-        buf[9] = mon[1];
-        buf[10] = mon[2];
-        buf[12] = b'0' + (self.year / 1000) as u8;
-        buf[13] = b'0' + (self.year / 100 % 10) as u8;
-        buf[14] = b'0' + (self.year / 10 % 10) as u8;
-        buf[15] = b'0' + (self.year % 10) as u8;
-        buf[17] = b'0' + (self.hour / 10);
-        buf[18] = b'0' + (self.hour % 10);
-        buf[20] = b'0' + (self.min / 10);
-        buf[21] = b'0' + (self.min % 10);
-        buf[23] = b'0' + (self.sec / 10);
-        buf[24] = b'0' + (self.sec % 10);
-        f.write_str(std::str::from_utf8(&buf[..]).unwrap())
-    }
+const MONTH_INFIXES: [&str; 12] = [
+    " Jan ", " Feb ", " Mar ", " Apr ", " May ", " Jun ", " Jul ", " Aug ", " Sep ", " Oct ",
+    " Nov ", " Dec ",
+];
+
+/// The numbers from 0 to 99 with two digits each.
+const DIGITS: &str = "\
+    00010203040506070809101112131415161718192021222324\
+    25262728293031323334353637383940414243444546474849\
+    50515253545556575859606162636465666768697071727374\
+    75767778798081828384858687888990919293949596979899";
+
+fn two_digits(n: u8) -> &'static str {
+    let i = usize::from(n) * 2;
+    &DIGITS[i..i + 2]
 }
 
-impl Ord for HttpDate {
-    fn cmp(&self, other: &HttpDate) -> cmp::Ordering {
-        SystemTime::from(*self).cmp(&SystemTime::from(*other))
-    }
+/// The date formats accepted by the parser.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Format {
+    ImfFixdate,
+    Rfc850,
+    Asctime,
 }
 
-impl PartialOrd for HttpDate {
-    fn partial_cmp(&self, other: &HttpDate) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
+impl Format {
+    /// Pick the only format that can match the input.
+    ///
+    /// The three letter weekday is followed by a comma in IMF-fixdate and
+    /// by a space in asctime, RFC 850 uses the full weekday name.
+    fn detect(s: &[u8]) -> Format {
+        match s.get(3) {
+            Some(b',') => Format::ImfFixdate,
+            Some(b' ') => Format::Asctime,
+            _ => Format::Rfc850,
+        }
+    }
+
+    fn parse(self, s: &[u8]) -> Result<Fields, Error> {
+        match self {
+            Format::ImfFixdate => parse_imf_fixdate(s),
+            Format::Rfc850 => parse_rfc850_date(s),
+            Format::Asctime => parse_asctime(s),
+        }
     }
 }
 
-fn toint_1(x: u8) -> Result<u8, Error> {
-    let result = x.wrapping_sub(b'0');
+fn toint_1(s: &[u8], at: usize) -> Result<u8, Error> {
+    let result = s[at].wrapping_sub(b'0');
     if result < 10 {
         Ok(result)
     } else {
-        Err(Error(()))
+        Err(Error::format(at))
     }
 }
 
-fn toint_2(s: &[u8]) -> Result<u8, Error> {
-    let high = s[0].wrapping_sub(b'0');
-    let low = s[1].wrapping_sub(b'0');
+fn toint_2(s: &[u8], at: usize) -> Result<u8, Error> {
+    let high = s[at].wrapping_sub(b'0');
+    let low = s[at + 1].wrapping_sub(b'0');
 
     if high < 10 && low < 10 {
         Ok(high * 10 + low)
     } else {
-        Err(Error(()))
+        Err(Error::format(at))
     }
 }
 
 #[allow(clippy::many_single_char_names)]
-fn toint_4(s: &[u8]) -> Result<u16, Error> {
-    let a = u16::from(s[0].wrapping_sub(b'0'));
-    let b = u16::from(s[1].wrapping_sub(b'0'));
-    let c = u16::from(s[2].wrapping_sub(b'0'));
-    let d = u16::from(s[3].wrapping_sub(b'0'));
+fn toint_4(s: &[u8], at: usize) -> Result<u16, Error> {
+    let a = u16::from(s[at].wrapping_sub(b'0'));
+    let b = u16::from(s[at + 1].wrapping_sub(b'0'));
+    let c = u16::from(s[at + 2].wrapping_sub(b'0'));
+    let d = u16::from(s[at + 3].wrapping_sub(b'0'));
 
     if a < 10 && b < 10 && c < 10 && d < 10 {
         Ok(a * 1000 + b * 100 + c * 10 + d)
     } else {
-        Err(Error(()))
+        Err(Error::format(at))
     }
 }
 
-fn parse_imf_fixdate(s: &[u8]) -> Result<HttpDate, Error> {
+/// Check that the input has exactly the expected length.
+fn expect_len(s: &[u8], len: usize) -> Result<(), Error> {
+    if s.len() == len {
+        Ok(())
+    } else {
+        Err(Error::format(s.len().min(len)))
+    }
+}
+
+/// Check that `lit` occurs at position `at` of the input.
+fn expect(s: &[u8], at: usize, lit: &[u8]) -> Result<(), Error> {
+    if &s[at..at + lit.len()] == lit {
+        Ok(())
+    } else {
+        Err(Error::format(at))
+    }
+}
+
+/// Load eight bytes starting at `at` as a little endian word.
+#[inline]
+fn load_u64(s: &[u8], at: usize) -> u64 {
+    let mut word = [0; 8];
+    word.copy_from_slice(&s[at..at + 8]);
+    u64::from_le_bytes(word)
+}
+
+/// Compare a word of ASCII bytes against a template.
+///
+/// Where `digits` has a set byte the input must be a digit and the
+/// template `b'0'`, all other bytes must equal the template. Returns the
+/// word with the digit values in place of the digits.
+#[inline]
+fn swar_match(word: u64, template: u64, digits: u64) -> Option<u64> {
+    // ASCII digits xor '0' are exactly the values 0 to 9, matching
+    // separators become 0. Adding 0x76 sets the high bit of larger bytes.
+    let x = word ^ template;
+    let invalid =
+        ((x.wrapping_add(0x7676_7676_7676_7676) | x) & 0x8080_8080_8080_8080) | (x & !digits);
+    if invalid == 0 {
+        Some(x)
+    } else {
+        None
+    }
+}
+
+/// Two digit number from the digit values at byte `at` of a word.
+#[inline]
+fn swar_2(x: u64, at: u32) -> u8 {
+    ((x >> (8 * at)) as u8) * 10 + (x >> (8 * (at + 1))) as u8
+}
+
+/// Parse an IMF-fixdate by checking eight bytes at a time.
+///
+/// Returns `None` on any error, the byte-wise parser then finds the offset.
+#[inline]
+fn parse_imf_fixdate_fast(s: &[u8]) -> Option<Fields> {
+    if s.len() != 29 || &s[25..] != b" GMT" {
+        return None;
+    }
+    // `1994 08:` and `08:49:37`, the windows overlap in the hour.
+    let ymd = swar_match(
+        load_u64(s, 12),
+        u64::from_le_bytes(*b"0000 00:"),
+        0x00ff_ff00_ffff_ffff,
+    )?;
+    let hms = swar_match(
+        load_u64(s, 17),
+        u64::from_le_bytes(*b"00:00:00"),
+        0xffff_00ff_ff00_ffff,
+    )?;
+    Some(Fields {
+        sec: swar_2(hms, 6),
+        min: swar_2(hms, 3),
+        hour: swar_2(hms, 0),
+        day: toint_2(s, 5).ok()?,
+        mon: imf_month(&s[7..12])?,
+        year: u16::from(swar_2(ymd, 0)) * 100 + u16::from(swar_2(ymd, 2)),
+        wday: imf_wday(&s[..5])?,
+    })
+}
+
+fn parse_imf_fixdate(s: &[u8]) -> Result<Fields, Error> {
     // Example: `Sun, 06 Nov 1994 08:49:37 GMT`
-    if s.len() != 29 || &s[25..] != b" GMT" || s[16] != b' ' || s[19] != b':' || s[22] != b':' {
-        return Err(Error(()));
-    }
-    Ok(HttpDate {
-        sec: toint_2(&s[23..25])?,
-        min: toint_2(&s[20..22])?,
-        hour: toint_2(&s[17..19])?,
-        day: toint_2(&s[5..7])?,
-        mon: match &s[7..12] {
-            b" Jan " => 1,
-            b" Feb " => 2,
-            b" Mar " => 3,
-            b" Apr " => 4,
-            b" May " => 5,
-            b" Jun " => 6,
-            b" Jul " => 7,
-            b" Aug " => 8,
-            b" Sep " => 9,
-            b" Oct " => 10,
-            b" Nov " => 11,
-            b" Dec " => 12,
-            _ => return Err(Error(())),
-        },
-        year: toint_4(&s[12..16])?,
-        wday: match &s[..5] {
-            b"Mon, " => 1,
-            b"Tue, " => 2,
-            b"Wed, " => 3,
-            b"Thu, " => 4,
-            b"Fri, " => 5,
-            b"Sat, " => 6,
-            b"Sun, " => 7,
-            _ => return Err(Error(())),
-        },
+    if let Some(date) = parse_imf_fixdate_fast(s) {
+        return Ok(date);
+    }
+    expect_len(s, 29)?;
+    expect(s, 16, b" ")?;
+    expect(s, 19, b":")?;
+    expect(s, 22, b":")?;
+    expect(s, 25, b" GMT")?;
+    Ok(Fields {
+        sec: toint_2(s, 23)?,
+        min: toint_2(s, 20)?,
+        hour: toint_2(s, 17)?,
+        day: toint_2(s, 5)?,
+        mon: imf_month(&s[7..12]).ok_or_else(|| Error::format(7))?,
+        year: toint_4(s, 12)?,
+        wday: imf_wday(&s[..5]).ok_or_else(|| Error::format(0))?,
     })
 }
 
-fn parse_rfc850_date(s: &[u8]) -> Result<HttpDate, Error> {
-    // Example: `Sunday, 06-Nov-94 08:49:37 GMT`
-    if s.len() < 23 {
-        return Err(Error(()));
+/// Parse an IMF-fixdate token by token, accepting the deviations allowed
+/// by `leniency`.
+fn parse_imf_fixdate_lenient(s: &[u8], leniency: Leniency) -> Result<Fields, Error> {
+    // Example: `Sun 06 Nov 1994 8:49:37 GMT`
+    let mut c = Cursor { s, at: 0 };
+    let wday = weekday(c.take(3)?).ok_or_else(|| Error::format(0))?;
+    if !(leniency.missing_comma && c.s.get(c.at) == Some(&b' ')) {
+        c.literal(b",")?;
+    }
+    c.literal(b" ")?;
+    let day = c.number(if leniency.short_day { 1 } else { 2 }, 2)? as u8;
+    c.literal(b" ")?;
+    let at = c.at;
+    let mon = if leniency.numeric_month && s.get(at).is_some_and(u8::is_ascii_digit) {
+        c.number(2, 2)? as u8
+    } else {
+        month(c.take(3)?).ok_or_else(|| Error::format(at))?
+    };
+    c.literal(b" ")?;
+    let year = c.number(4, 4)?;
+    c.literal(b" ")?;
+    let min_digits = if leniency.short_time { 1 } else { 2 };
+    let hour = c.number(min_digits, 2)? as u8;
+    c.literal(b":")?;
+    let min = c.number(min_digits, 2)? as u8;
+    c.literal(b":")?;
+    let sec = c.number(min_digits, 2)? as u8;
+    c.literal(b" GMT")?;
+    if c.at != s.len() {
+        return Err(Error::format(c.at));
+    }
+    Ok(Fields {
+        sec,
+        min,
+        hour,
+        day,
+        mon,
+        year,
+        wday,
+    })
+}
+
+/// Reads tokens from the start of a date.
+struct Cursor<'a> {
+    s: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let token = self
+            .s
+            .get(self.at..self.at + len)
+            .ok_or_else(|| Error::format(self.s.len()))?;
+        self.at += len;
+        Ok(token)
     }
 
-    fn wday<'a>(s: &'a [u8], wday: u8, name: &'static [u8]) -> Option<(u8, &'a [u8])> {
-        if &s[0..name.len()] == name {
-            return Some((wday, &s[name.len()..]));
+    fn literal(&mut self, lit: &[u8]) -> Result<(), Error> {
+        let at = self.at;
+        if self.take(lit.len())? == lit {
+            Ok(())
+        } else {
+            Err(Error::format(at))
         }
+    }
+
+    /// A decimal number with `min` to `max` digits.
+    fn number(&mut self, min: usize, max: usize) -> Result<u16, Error> {
+        let digits = self.s[self.at..]
+            .iter()
+            .take(max)
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits < min {
+            return Err(Error::format(self.at));
+        }
+        let n = self.s[self.at..self.at + digits]
+            .iter()
+            .fold(0, |n, &b| n * 10 + u16::from(b - b'0'));
+        self.at += digits;
+        Ok(n)
+    }
+}
+
+fn imf_month(s: &[u8]) -> Option<u8> {
+    // Example: ` Nov `
+    if s[0] == b' ' && s[4] == b' ' {
+        month(&s[1..4])
+    } else {
         None
     }
-    let (wday, s) = wday(s, 1, b"Monday, ")
-        .or_else(|| wday(s, 2, b"Tuesday, "))
-        .or_else(|| wday(s, 3, b"Wednesday, "))
-        .or_else(|| wday(s, 4, b"Thursday, "))
-        .or_else(|| wday(s, 5, b"Friday, "))
-        .or_else(|| wday(s, 6, b"Saturday, "))
-        .or_else(|| wday(s, 7, b"Sunday, "))
-        .ok_or(Error(()))?;
-    if s.len() != 22 || s[12] != b':' || s[15] != b':' || &s[18..22] != b" GMT" {
-        return Err(Error(()));
-    }
-    let mut year = u16::from(toint_2(&s[7..9])?);
+}
+
+fn imf_wday(s: &[u8]) -> Option<u8> {
+    // Example: `Sun, `
+    if &s[3..5] == b", " {
+        weekday(&s[..3])
+    } else {
+        None
+    }
+}
+
+fn parse_rfc850_date(s: &[u8]) -> Result<Fields, Error> {
+    // Example: `Sunday, 06-Nov-94 08:49:37 GMT`
+    if s.len() < 23 {
+        return Err(Error::format(s.len()));
+    }
+
+    let wday = weekday(&s[..3]).ok_or_else(|| Error::format(0))?;
+    let name = WEEKDAY_NAMES[usize::from(wday - 1)].as_bytes();
+    let o = name.len() + 2;
+    if &s[..name.len()] != name || &s[name.len()..o] != b", " {
+        return Err(Error::format(0));
+    }
+    expect_len(s, o + 22)?;
+    expect(s, o + 12, b":")?;
+    expect(s, o + 15, b":")?;
+    expect(s, o + 18, b" GMT")?;
+    let mut year = u16::from(toint_2(s, o + 7)?);
     if year < 70 {
         year += 2000;
     } else {
         year += 1900;
     }
-    Ok(HttpDate {
-        sec: toint_2(&s[16..18])?,
-        min: toint_2(&s[13..15])?,
-        hour: toint_2(&s[10..12])?,
-        day: toint_2(&s[0..2])?,
-        mon: match &s[2..7] {
-            b"-Jan-" => 1,
-            b"-Feb-" => 2,
-            b"-Mar-" => 3,
-            b"-Apr-" => 4,
-            b"-May-" => 5,
-            b"-Jun-" => 6,
-            b"-Jul-" => 7,
-            b"-Aug-" => 8,
-            b"-Sep-" => 9,
-            b"-Oct-" => 10,
-            b"-Nov-" => 11,
-            b"-Dec-" => 12,
-            _ => return Err(Error(())),
-        },
+    Ok(Fields {
+        sec: toint_2(s, o + 16)?,
+        min: toint_2(s, o + 13)?,
+        hour: toint_2(s, o + 10)?,
+        day: toint_2(s, o)?,
+        mon: if s[o + 2] == b'-' && s[o + 6] == b'-' {
+            month(&s[o + 3..o + 6])
+        } else {
+            None
+        }
+        .ok_or_else(|| Error::format(o + 2))?,
         year,
         wday,
     })
 }
 
-fn parse_asctime(s: &[u8]) -> Result<HttpDate, Error> {
+fn parse_asctime(s: &[u8]) -> Result<Fields, Error> {
     // Example: `Sun Nov  6 08:49:37 1994`
-    if s.len() != 24 || s[10] != b' ' || s[13] != b':' || s[16] != b':' || s[19] != b' ' {
-        return Err(Error(()));
-    }
-    Ok(HttpDate {
-        sec: toint_2(&s[17..19])?,
-        min: toint_2(&s[14..16])?,
-        hour: toint_2(&s[11..13])?,
+    expect_len(s, 24)?;
+    expect(s, 10, b" ")?;
+    expect(s, 13, b":")?;
+    expect(s, 16, b":")?;
+    expect(s, 19, b" ")?;
+    Ok(Fields {
+        sec: toint_2(s, 17)?,
+        min: toint_2(s, 14)?,
+        hour: toint_2(s, 11)?,
         day: {
-            let x = &s[8..10];
-            {
-                if x[0] == b' ' {
-                    toint_1(x[1])
-                } else {
-                    toint_2(x)
-                }
-            }?
-        },
-        mon: match &s[4..8] {
-            b"Jan " => 1,
-            b"Feb " => 2,
-            b"Mar " => 3,
-            b"Apr " => 4,
-            b"May " => 5,
-            b"Jun " => 6,
-            b"Jul " => 7,
-            b"Aug " => 8,
-            b"Sep " => 9,
-            b"Oct " => 10,
-            b"Nov " => 11,
-            b"Dec " => 12,
-            _ => return Err(Error(())),
-        },
-        year: toint_4(&s[20..24])?,
-        wday: match &s[0..4] {
-            b"Mon " => 1,
-            b"Tue " => 2,
-            b"Wed " => 3,
-            b"Thu " => 4,
-            b"Fri " => 5,
-            b"Sat " => 6,
-            b"Sun " => 7,
-            _ => return Err(Error(())),
-        },
+            if s[8] == b' ' {
+                toint_1(s, 9)
+            } else {
+                toint_2(s, 8)
+            }
+        }?,
+        mon: if s[7] == b' ' { month(&s[4..7]) } else { None }.ok_or_else(|| Error::format(4))?,
+        year: toint_4(s, 20)?,
+        wday: if s[3] == b' ' { weekday(&s[..3]) } else { None }.ok_or_else(|| Error::format(0))?,
     })
 }
 
-fn is_leap_year(y: u16) -> bool {
-    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+const MONTHS: [[u8; 3]; 12] = [
+    *b"Jan", *b"Feb", *b"Mar", *b"Apr", *b"May", *b"Jun", *b"Jul", *b"Aug", *b"Sep", *b"Oct",
+    *b"Nov", *b"Dec",
+];
+
+const WEEKDAYS: [[u8; 3]; 7] = [
+    *b"Mon", *b"Tue", *b"Wed", *b"Thu", *b"Fri", *b"Sat", *b"Sun",
+];
+
+pub(crate) const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Multipliers that hash the names to distinct slots of a 16 entry table.
+const MONTH_MUL: u32 = 26596;
+const WEEKDAY_MUL: u32 = 1261;
+
+const MONTH_SLOTS: [u8; 16] = slots(&MONTHS, MONTH_MUL);
+const WEEKDAY_SLOTS: [u8; 16] = slots(&WEEKDAYS, WEEKDAY_MUL);
+
+const fn pack(name: [u8; 3]) -> u32 {
+    name[0] as u32 | (name[1] as u32) << 8 | (name[2] as u32) << 16
+}
+
+const fn slot(packed: u32, mul: u32) -> usize {
+    (packed.wrapping_mul(mul) >> 28) as usize
+}
+
+/// Build a table from hash slot to one-based index of the name.
+const fn slots<const N: usize>(names: &[[u8; 3]; N], mul: u32) -> [u8; 16] {
+    let mut table = [0; 16];
+    let mut i = 0;
+    while i < N {
+        table[slot(pack(names[i]), mul)] = i as u8 + 1;
+        i += 1;
+    }
+    table
+}
+
+/// Look up a three letter name, returning its one-based index.
+#[inline]
+fn lookup(s: &[u8], names: &[[u8; 3]], slots: &[u8; 16], mul: u32) -> Option<u8> {
+    let name = [s[0], s[1], s[2]];
+    let index = slots[slot(pack(name), mul)];
+    if index != 0 && names[usize::from(index - 1)] == name {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// Month number of a three letter month name like `Nov`.
+pub(crate) fn month(s: &[u8]) -> Option<u8> {
+    lookup(s, &MONTHS, &MONTH_SLOTS, MONTH_MUL)
+}
+
+/// Weekday number, Monday is 1, of a three letter name like `Sun`.
+pub(crate) fn weekday(s: &[u8]) -> Option<u8> {
+    lookup(s, &WEEKDAYS, &WEEKDAY_SLOTS, WEEKDAY_MUL)
+}
+
+/// Split a date ending in `GMT+hhmm` or `GMT-hhmm` after `GMT`, returning
+/// the offset in seconds.
+fn split_zone_offset(s: &[u8]) -> Option<(&[u8], i64)> {
+    let trailing = s
+        .iter()
+        .rev()
+        .take_while(|&&b| b.is_ascii() && is_space(b))
+        .count();
+    let s = &s[..s.len() - trailing];
+    let at = s.len().checked_sub(5)?;
+    if !s[..at].ends_with(b"GMT") {
+        return None;
+    }
+    let sign = match s[at] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let hours = toint_2(s, at + 1).ok().filter(|&h| h < 24)?;
+    let mins = toint_2(s, at + 3).ok().filter(|&m| m < 60)?;
+    Some((
+        &s[..at],
+        sign * (i64::from(hours) * 3600 + i64::from(mins) * 60),
+    ))
+}
+
+/// Strip surrounding whitespace, returning the number of leading bytes removed.
+fn trim(s: &[u8]) -> (usize, &[u8]) {
+    let base = s.iter().take_while(|&&b| is_space(b)).count();
+    let trailing = s[base..].iter().rev().take_while(|&&b| is_space(b)).count();
+    (base, &s[base..s.len() - trailing])
+}
+
+/// ASCII whitespace as understood by `str::trim`.
+fn is_space(b: u8) -> bool {
+    (b as char).is_whitespace()
 }
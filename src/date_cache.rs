@@ -0,0 +1,144 @@
+use std::fmt::Write;
+
+use crate::clock::{Clock, SystemClock};
+use crate::HttpDate;
+
+/// Cache for the value of the `Date` header field.
+///
+/// Servers send the same date in every response generated in the same
+/// second. The cache formats the date only when the second changes. Keep
+/// one cache per thread, for example in a `thread_local!`.
+///
+/// ```
+/// # use httpdate::{DateHeaderCache, HttpDate};
+/// let mut cache = DateHeaderCache::new();
+/// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert_eq!(cache.get_at(date), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert_eq!(cache.get().len(), 29);
+/// ```
+///
+/// The cache reads the system clock by default. On platforms where
+/// `SystemTime::now()` does not work pass another [`Clock`], for example a
+/// function returning the seconds since the epoch:
+///
+/// ```
+/// # use httpdate::DateHeaderCache;
+/// use httpdate::clock::RtcClock;
+///
+/// fn host_time() -> u64 {
+///     784111777
+/// }
+///
+/// let mut cache = DateHeaderCache::with_clock(RtcClock(host_time));
+/// assert_eq!(cache.get(), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DateHeaderCache<C = SystemClock> {
+    clock: C,
+    date: Option<HttpDate>,
+    formatted: String,
+    #[cfg(feature = "http")]
+    value: Option<::http::HeaderValue>,
+}
+
+impl DateHeaderCache {
+    /// Create an empty cache that reads the system clock.
+    pub fn new() -> DateHeaderCache {
+        DateHeaderCache::default()
+    }
+}
+
+impl<C: Clock> DateHeaderCache<C> {
+    /// Create an empty cache that reads `clock`.
+    pub fn with_clock(clock: C) -> DateHeaderCache<C> {
+        DateHeaderCache {
+            clock,
+            date: None,
+            formatted: String::new(),
+            #[cfg(feature = "http")]
+            value: None,
+        }
+    }
+
+    /// The current date formatted as IMF-fixdate.
+    pub fn get(&mut self) -> &str {
+        self.get_at(self.clock.now())
+    }
+
+    /// The time of `clock` formatted as IMF-fixdate.
+    pub fn get_from<K: Clock>(&mut self, clock: &K) -> &str {
+        self.get_at(clock.now())
+    }
+
+    /// The given date formatted as IMF-fixdate.
+    pub fn get_at(&mut self, now: HttpDate) -> &str {
+        self.update(now);
+        &self.formatted
+    }
+
+    /// The current date as a header value.
+    ///
+    /// Only available with the `http` feature. Cloning a header value
+    /// only increments a reference count.
+    #[cfg(feature = "http")]
+    pub fn header_value(&mut self) -> ::http::HeaderValue {
+        self.header_value_at(self.clock.now())
+    }
+
+    /// The given date as a header value.
+    ///
+    /// Only available with the `http` feature.
+    #[cfg(feature = "http")]
+    pub fn header_value_at(&mut self, now: HttpDate) -> ::http::HeaderValue {
+        self.update(now);
+        let formatted = &self.formatted;
+        self.value
+            .get_or_insert_with(|| {
+                ::http::HeaderValue::from_str(formatted)
+                    .expect("IMF-fixdate is a valid header value")
+            })
+            .clone()
+    }
+
+    fn update(&mut self, now: HttpDate) {
+        if self.date != Some(now) {
+            self.date = Some(now);
+            self.formatted.clear();
+            write!(self.formatted, "{}", now).expect("writing to a String does not fail");
+            #[cfg(feature = "http")]
+            {
+                self.value = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::DateHeaderCache;
+    use crate::clock::{FixedClock, MockClock};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_date_header_cache() {
+        let mut cache = DateHeaderCache::new();
+        let d1: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let d2: HttpDate = "Sun, 06 Nov 1994 08:49:38 GMT".parse().unwrap();
+        assert_eq!(cache.get_at(d1), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(cache.get_at(d1), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(cache.get_at(d2), "Sun, 06 Nov 1994 08:49:38 GMT");
+        let clock = MockClock::new(d1);
+        assert_eq!(cache.get_from(&clock), "Sun, 06 Nov 1994 08:49:37 GMT");
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(cache.get_from(&clock), "Sun, 06 Nov 1994 08:49:39 GMT");
+        #[cfg(feature = "http")]
+        {
+            assert_eq!(cache.header_value_at(d2), "Sun, 06 Nov 1994 08:49:38 GMT");
+            assert_eq!(cache.header_value_at(d1), "Sun, 06 Nov 1994 08:49:37 GMT");
+        }
+        let mut cache = DateHeaderCache::with_clock(FixedClock(d2));
+        assert_eq!(cache.get(), "Sun, 06 Nov 1994 08:49:38 GMT");
+    }
+}
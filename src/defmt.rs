@@ -0,0 +1,23 @@
+//! `defmt::Format` implementation, enabled with the `defmt` feature.
+
+use ::defmt::{Format, Formatter};
+
+use crate::HttpDate;
+
+impl Format for HttpDate {
+    /// Sends the fields in binary form, the host renders them
+    /// as `1994-11-06T08:49:37Z`.
+    fn format(&self, f: Formatter) {
+        let d = self.fields();
+        ::defmt::write!(
+            f,
+            "{=u16}-{=u8:02}-{=u8:02}T{=u8:02}:{=u8:02}:{=u8:02}Z",
+            d.year,
+            d.mon,
+            d.day,
+            d.hour,
+            d.min,
+            d.sec
+        )
+    }
+}
@@ -0,0 +1,145 @@
+//! Delta-seconds values used by `Cache-Control`, `Age`, `Keep-Alive` and
+//! other fields.
+//!
+//! A delta-seconds value is a non-negative number of seconds, see RFC 9111
+//! section 1.2.2. Values larger than 2<sup>31</sup> seconds are clamped to
+//! 2<sup>31</sup> seconds as the specification requires.
+
+use core::time::Duration;
+
+use crate::list::split_list;
+use crate::Error;
+
+/// Largest delta-seconds value in seconds.
+pub(crate) const MAX_SECS: u64 = 1 << 31;
+
+/// Largest delta-seconds value, 2<sup>31</sup> seconds.
+pub const MAX: Duration = Duration::from_secs(MAX_SECS);
+
+/// Parse a non-negative number of seconds, clamping it to `MAX_SECS`.
+pub(crate) fn parse_secs(s: &[u8]) -> Result<u64, Error> {
+    if s.is_empty() {
+        return Err(Error::format(0));
+    }
+    let mut n: u64 = 0;
+    for (i, &b) in s.iter().enumerate() {
+        if !b.is_ascii_digit() {
+            return Err(Error::format(i));
+        }
+        n = (n * 10 + u64::from(b - b'0')).min(MAX_SECS);
+    }
+    Ok(n)
+}
+
+/// Parse a delta-seconds value.
+///
+/// Surrounding whitespace and the quoted form `"60"`, which senders
+/// should not generate but some do, are accepted.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::delta_seconds;
+/// assert_eq!(delta_seconds::parse("60"), Ok(Duration::from_secs(60)));
+/// assert_eq!(delta_seconds::parse("\"60\""), Ok(Duration::from_secs(60)));
+/// assert_eq!(delta_seconds::parse("99999999999999"), Ok(delta_seconds::MAX));
+/// assert!(delta_seconds::parse("-1").is_err());
+/// ```
+pub fn parse(value: &str) -> Result<Duration, Error> {
+    let s = value.trim().as_bytes();
+    let s = match s {
+        [b'"', inner @ .., b'"'] => inner,
+        _ => s,
+    };
+    parse_secs(s).map(Duration::from_secs)
+}
+
+/// Find and parse the `max-age` directive of a `Cache-Control` header field.
+///
+/// Returns `None` if the directive is missing. If it occurs more than
+/// once the first occurrence is used.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::delta_seconds;
+/// let cache_control = "public, max-age=3600, s-maxage=60";
+/// assert_eq!(delta_seconds::max_age(cache_control), Some(Ok(Duration::from_secs(3600))));
+/// assert_eq!(delta_seconds::s_maxage(cache_control), Some(Ok(Duration::from_secs(60))));
+/// ```
+pub fn max_age(cache_control: &str) -> Option<Result<Duration, Error>> {
+    directive(cache_control, "max-age")
+}
+
+/// Find and parse the `s-maxage` directive of a `Cache-Control` header field.
+///
+/// Returns `None` if the directive is missing. If it occurs more than
+/// once the first occurrence is used.
+pub fn s_maxage(cache_control: &str) -> Option<Result<Duration, Error>> {
+    directive(cache_control, "s-maxage")
+}
+
+/// Find and parse the `timeout` parameter of a `Keep-Alive` header field.
+///
+/// Returns `None` if the parameter is missing.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::delta_seconds;
+/// assert_eq!(
+///     delta_seconds::keep_alive_timeout("timeout=5, max=1000"),
+///     Some(Ok(Duration::from_secs(5)))
+/// );
+/// ```
+pub fn keep_alive_timeout(keep_alive: &str) -> Option<Result<Duration, Error>> {
+    directive(keep_alive, "timeout")
+}
+
+fn directive(cache_control: &str, name: &str) -> Option<Result<Duration, Error>> {
+    split_list(cache_control).find_map(|d| {
+        let (key, value) = match d.find('=') {
+            Some(i) => (&d[..i], Some(&d[i + 1..])),
+            None => (d, None),
+        };
+        if !key.trim().eq_ignore_ascii_case(name) {
+            return None;
+        }
+        Some(value.map_or_else(|| Err(Error::format(d.len())), parse))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::{keep_alive_timeout, max_age, parse, s_maxage, MAX};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("0"), Ok(Duration::from_secs(0)));
+        assert_eq!(parse(" 42 "), Ok(Duration::from_secs(42)));
+        assert_eq!(parse("\"42\""), Ok(Duration::from_secs(42)));
+        assert_eq!(parse("2147483648"), Ok(MAX));
+        assert_eq!(parse("2147483649"), Ok(MAX));
+        assert_eq!(parse("184467440737095516150"), Ok(MAX));
+        assert!(parse("").is_err());
+        assert!(parse("\"\"").is_err());
+        assert!(parse("\"42").is_err());
+        assert!(parse("4 2").is_err());
+        assert!(parse("+42").is_err());
+        assert!(parse("42s").is_err());
+    }
+
+    #[test]
+    fn test_directives() {
+        let secs = |s| Some(Ok(Duration::from_secs(s)));
+        assert_eq!(max_age("max-age=60"), secs(60));
+        assert_eq!(max_age("no-cache, MAX-AGE = \"60\" "), secs(60));
+        assert_eq!(max_age("max-age=60, max-age=10"), secs(60));
+        assert_eq!(max_age("s-maxage=60"), None);
+        assert_eq!(max_age(""), None);
+        assert!(max_age("max-age").unwrap().is_err());
+        assert!(max_age("max-age=soon").unwrap().is_err());
+        assert_eq!(s_maxage("private=\"a, s-maxage=1\", s-maxage=5"), secs(5));
+        assert_eq!(keep_alive_timeout("max=1000, timeout=5"), secs(5));
+        assert_eq!(keep_alive_timeout("max=1000"), None);
+    }
+}
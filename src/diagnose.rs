@@ -0,0 +1,365 @@
+use crate::calendar::Weekday;
+use crate::date::{month, weekday, WEEKDAY_NAMES};
+use crate::rfc3339::from_local;
+use crate::HttpDate;
+
+/// A problem found by [`HttpDate::diagnose`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Problem {
+    /// The value has leading or trailing whitespace.
+    SurroundingWhitespace,
+    /// The value contains bytes that are not ASCII.
+    NonAscii,
+    /// The value uses the obsolete RFC 850 or asctime format.
+    ObsoleteFormat,
+    /// The value does not have the parts of an IMF-fixdate, RFC 850 or
+    /// asctime date.
+    Unrecognized,
+    /// The parts are not separated by single spaces.
+    BadSpacing,
+    /// The weekday is not followed by a comma.
+    MissingComma,
+    /// The weekday is not a three letter name like `Sun`, or a full name
+    /// like `Sunday` in RFC 850 dates.
+    UnknownWeekday,
+    /// The weekday does not match the date.
+    WrongWeekday {
+        /// The weekday of the date.
+        expected: Weekday,
+    },
+    /// The day is not two digits from `01` to `31`, asctime dates may
+    /// have a single digit.
+    BadDay,
+    /// The month is not a three letter name like `Nov`.
+    UnknownMonth,
+    /// The year is not four digits from `1970` to `9999`, or two digits
+    /// in RFC 850 dates.
+    BadYear,
+    /// The month does not have this day, like 30 February.
+    NoSuchDay,
+    /// The time is not a valid `HH:MM:SS`.
+    BadTime,
+    /// The time zone is not `GMT`.
+    BadZone,
+}
+
+impl HttpDate {
+    /// List every problem with a date, for reports on non-compliant servers.
+    ///
+    /// Unlike parsing this does not stop at the first problem. The fields
+    /// of IMF-fixdate, RFC 850 and asctime dates are checked one by one.
+    /// An empty list means the value is a valid IMF-fixdate.
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// # use httpdate::{calendar::Weekday, HttpDate, Problem};
+    /// assert_eq!(
+    ///     HttpDate::diagnose("Mon, 30 Feb 2016 08:49:37 UTC"),
+    ///     [Problem::NoSuchDay, Problem::BadZone]
+    /// );
+    /// assert_eq!(
+    ///     HttpDate::diagnose("Mon, 06 Nov 1994  08:49:37 GMT"),
+    ///     [Problem::BadSpacing, Problem::WrongWeekday { expected: Weekday::Sunday }]
+    /// );
+    /// assert!(HttpDate::diagnose("Sun, 06 Nov 1994 08:49:37 GMT").is_empty());
+    /// ```
+    pub fn diagnose(s: &str) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let t = s.trim_matches(|c: char| c.is_ascii() && c.is_whitespace());
+        if t.len() != s.len() {
+            problems.push(Problem::SurroundingWhitespace);
+        }
+        if !s.is_ascii() {
+            problems.push(Problem::NonAscii);
+            return problems;
+        }
+        let obsolete = t.len() < 4 || t.as_bytes()[3] != b',';
+        if HttpDate::parse_bytes(t.as_bytes()).is_ok() {
+            if obsolete {
+                problems.push(Problem::ObsoleteFormat);
+            }
+            return problems;
+        }
+        diagnose_fields(t, &mut problems);
+        problems
+    }
+}
+
+fn diagnose_fields(s: &str, problems: &mut Vec<Problem>) {
+    let (wday, comma, rest) = match s.find([',', ' ']) {
+        Some(i) if s.as_bytes()[i] == b',' => (&s[..i], true, &s[i + 1..]),
+        Some(i) => (&s[..i], false, &s[i..]),
+        None => {
+            problems.push(Problem::Unrecognized);
+            return;
+        }
+    };
+    let parts: Vec<&str> = rest.split_whitespace().collect();
+    match parts[..] {
+        [date, time, zone] if date.contains('-') => {
+            problems.push(Problem::ObsoleteFormat);
+            diagnose_rfc850(wday, comma, [date, time, zone], rest, problems);
+        }
+        [mon, day, time, year] if !comma => {
+            problems.push(Problem::ObsoleteFormat);
+            diagnose_asctime(wday, [mon, day, time, year], rest, problems);
+        }
+        [day, mon, year, time, zone] => {
+            diagnose_imf_fixdate(wday, comma, [day, mon, year, time, zone], rest, problems);
+        }
+        _ => {
+            if wday.len() > 3 || parts.first().is_some_and(|p| month(p.as_bytes()).is_some()) {
+                problems.push(Problem::ObsoleteFormat);
+            }
+            problems.push(Problem::Unrecognized);
+        }
+    }
+}
+
+fn diagnose_imf_fixdate(
+    wday: &str,
+    comma: bool,
+    parts: [&str; 5],
+    rest: &str,
+    problems: &mut Vec<Problem>,
+) {
+    // Example: `Sun, 06 Nov 1994 08:49:37 GMT`
+    let [day, mon, year, time, zone] = parts;
+    if !comma {
+        problems.push(Problem::MissingComma);
+    }
+    check_spacing(
+        rest,
+        &format!(" {} {} {} {} {}", day, mon, year, time, zone),
+        problems,
+    );
+    let wday = weekday(wday.as_bytes());
+    if wday.is_none() {
+        problems.push(Problem::UnknownWeekday);
+    }
+    let day = two_digits(day);
+    let year = Some(year)
+        .filter(|y| y.len() == 4 && y.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|y| y.parse::<i64>().ok());
+    check_date(wday, day, mon, year, problems);
+    check_time(time, problems);
+    check_zone(zone, problems);
+}
+
+fn diagnose_rfc850(
+    wday: &str,
+    comma: bool,
+    parts: [&str; 3],
+    rest: &str,
+    problems: &mut Vec<Problem>,
+) {
+    // Example: `Sunday, 06-Nov-94 08:49:37 GMT`
+    let [date, time, zone] = parts;
+    if !comma {
+        problems.push(Problem::MissingComma);
+    }
+    check_spacing(rest, &format!(" {} {} {}", date, time, zone), problems);
+    let wday = WEEKDAY_NAMES
+        .iter()
+        .position(|&name| name == wday)
+        .map(|i| i as u8 + 1);
+    if wday.is_none() {
+        problems.push(Problem::UnknownWeekday);
+    }
+    let mut fields = date.splitn(3, '-');
+    let day = fields.next().and_then(two_digits);
+    let mon = fields.next().unwrap_or("");
+    // Two digit years are read as 1970 to 2069 like the parser does.
+    let year = fields
+        .next()
+        .and_then(two_digits)
+        .map(|y| if y < 70 { 2000 + y } else { 1900 + y });
+    check_date(wday, day, mon, year, problems);
+    check_time(time, problems);
+    check_zone(zone, problems);
+}
+
+fn diagnose_asctime(wday: &str, parts: [&str; 4], rest: &str, problems: &mut Vec<Problem>) {
+    // Example: `Sun Nov  6 08:49:37 1994`
+    let [mon, day, time, year] = parts;
+    check_spacing(
+        rest,
+        &format!(" {} {:>2} {} {}", mon, day, time, year),
+        problems,
+    );
+    let wday = weekday(wday.as_bytes());
+    if wday.is_none() {
+        problems.push(Problem::UnknownWeekday);
+    }
+    let day = Some(day)
+        .filter(|d| (1..=2).contains(&d.len()) && d.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|d| d.parse().ok());
+    let year = Some(year)
+        .filter(|y| y.len() == 4 && y.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|y| y.parse::<i64>().ok());
+    check_date(wday, day, mon, year, problems);
+    check_time(time, problems);
+}
+
+/// Compare the separators with the expected layout of the parts.
+fn check_spacing(rest: &str, expected: &str, problems: &mut Vec<Problem>) {
+    if rest != expected {
+        problems.push(Problem::BadSpacing);
+    }
+}
+
+/// Check the day, month and year and that the weekday matches them.
+fn check_date(
+    wday: Option<u8>,
+    day: Option<i64>,
+    mon: &str,
+    year: Option<i64>,
+    problems: &mut Vec<Problem>,
+) {
+    let day = day.filter(|d| (1..=31).contains(d));
+    if day.is_none() {
+        problems.push(Problem::BadDay);
+    }
+    let mon = month(mon.as_bytes());
+    if mon.is_none() {
+        problems.push(Problem::UnknownMonth);
+    }
+    let year = year.filter(|y| (1970..=9999).contains(y));
+    if year.is_none() {
+        problems.push(Problem::BadYear);
+    }
+    if let (Some(day), Some(mon), Some(year)) = (day, mon, year) {
+        match from_local(year, i64::from(mon), day, 0, 0, 0, 0) {
+            Ok(date) => {
+                let expected = date.fields().wday;
+                if wday.is_some_and(|w| w != expected) {
+                    problems.push(Problem::WrongWeekday {
+                        expected: Weekday::from_number(expected).expect("valid weekday"),
+                    });
+                }
+            }
+            Err(_) => problems.push(Problem::NoSuchDay),
+        }
+    }
+}
+
+fn check_time(time: &str, problems: &mut Vec<Problem>) {
+    let time: Vec<_> = time.split(':').map(two_digits).collect();
+    if !matches!(time[..], [Some(h), Some(m), Some(s)] if h < 24 && m < 60 && s < 60) {
+        problems.push(Problem::BadTime);
+    }
+}
+
+fn check_zone(zone: &str, problems: &mut Vec<Problem>) {
+    if zone != "GMT" {
+        problems.push(Problem::BadZone);
+    }
+}
+
+fn two_digits(s: &str) -> Option<i64> {
+    if s.len() == 2 && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Problem::*;
+    use crate::calendar::Weekday;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_diagnose() {
+        assert_eq!(HttpDate::diagnose("Sun, 06 Nov 1994 08:49:37 GMT"), []);
+        assert_eq!(
+            HttpDate::diagnose(" Sun Nov  6 08:49:37 1994"),
+            [SurroundingWhitespace, ObsoleteFormat]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sunday, 06-Nov-94 08:49:37 GMT"),
+            [ObsoleteFormat]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sunday, 31-Nov-94 08:49:37 GMT"),
+            [ObsoleteFormat, NoSuchDay]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Monday, 06-Nov-94 08:49:37 GMT"),
+            [
+                ObsoleteFormat,
+                WrongWeekday {
+                    expected: Weekday::Sunday
+                }
+            ]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sun, 6-Nov-1994 24:00:00 UTC"),
+            [
+                ObsoleteFormat,
+                UnknownWeekday,
+                BadDay,
+                BadYear,
+                BadTime,
+                BadZone
+            ]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Mon Feb 30 08:49:37 2016"),
+            [ObsoleteFormat, NoSuchDay]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Mon Nov 6 08:49:37 1994"),
+            [
+                ObsoleteFormat,
+                BadSpacing,
+                WrongWeekday {
+                    expected: Weekday::Sunday
+                }
+            ]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sun Nov  6 8:49:37 1969"),
+            [ObsoleteFormat, BadYear, BadTime]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sun, 06 Nov 1994 08:49:37 GMT\u{a0}"),
+            [NonAscii]
+        );
+        assert_eq!(HttpDate::diagnose("yesterday"), [Unrecognized]);
+        assert_eq!(
+            HttpDate::diagnose("Sun 06 Nov 1994 08:49:37 GMT"),
+            [MissingComma]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sun,06 Nov 1994\t08:49:37 GMT"),
+            [BadSpacing]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Xyz, 6 nov 1969 24:00:00 +0000"),
+            [
+                UnknownWeekday,
+                BadDay,
+                UnknownMonth,
+                BadYear,
+                BadTime,
+                BadZone
+            ]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Tue, 29 Feb 2100 08:49:37 GMT"),
+            [NoSuchDay]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Sat, 29 Feb 2000 08:49:37 GMT"),
+            [WrongWeekday {
+                expected: Weekday::Tuesday
+            }]
+        );
+        assert_eq!(
+            HttpDate::diagnose("Tue, 29 Feb 2000 08:60:37 GMT"),
+            [BadTime]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::date::Fields;
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+impl HttpDate {
+    /// Convert an MS-DOS date and time, as stored in ZIP archives and on
+    /// FAT file systems.
+    ///
+    /// The date packs the years since 1980, the month and the day, the time
+    /// packs the hour, minute and seconds divided by two. DOS times are in
+    /// local time of the machine that wrote them but carry no time zone,
+    /// they are read as UTC here. Returns an out of range error for invalid
+    /// fields such as the zero date.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::from_dos_datetime(0x1D66, 0x4632).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:36 GMT");
+    /// assert_eq!(date.to_dos_datetime().unwrap(), (0x1D66, 0x4632));
+    /// ```
+    pub fn from_dos_datetime(date: u16, time: u16) -> Result<HttpDate, Error> {
+        Fields {
+            sec: (time & 0x1F) as u8 * 2,
+            min: (time >> 5 & 0x3F) as u8,
+            hour: (time >> 11) as u8,
+            day: (date & 0x1F) as u8,
+            mon: (date >> 5 & 0x0F) as u8,
+            year: (date >> 9) + 1980,
+            wday: 1,
+        }
+        .validate_any_weekday()
+    }
+
+    /// The date as an MS-DOS date and time, rounding odd seconds down.
+    ///
+    /// Returns an out of range error for dates before 1980 or after 2107.
+    pub fn to_dos_datetime(&self) -> Result<(u16, u16), Error> {
+        let f = self.fields();
+        if !(1980..=2107).contains(&f.year) {
+            return Err(Error::new(ErrorKind::Range, None));
+        }
+        let date = (f.year - 1980) << 9 | u16::from(f.mon) << 5 | u16::from(f.day);
+        let time = u16::from(f.hour) << 11 | u16::from(f.min) << 5 | u16::from(f.sec / 2);
+        Ok((date, time))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use crate::HttpDate;
+
+    #[test]
+    fn test_dos_datetime() {
+        let first = HttpDate::from_dos_datetime(0x0021, 0).unwrap();
+        assert_eq!(first.to_string(), "Tue, 01 Jan 1980 00:00:00 GMT");
+        assert_eq!(first.to_dos_datetime().unwrap(), (0x0021, 0));
+        let last = HttpDate::from_dos_datetime(0xFF9F, 0xBF7D).unwrap();
+        assert_eq!(last.to_string(), "Sat, 31 Dec 2107 23:59:58 GMT");
+        let odd: HttpDate = "Sat, 31 Dec 2107 23:59:59 GMT".parse().unwrap();
+        assert_eq!(odd.to_dos_datetime().unwrap(), (0xFF9F, 0xBF7D));
+
+        assert!(HttpDate::from_dos_datetime(0, 0)
+            .unwrap_err()
+            .is_out_of_range());
+        // 30 February
+        assert!(HttpDate::from_dos_datetime(0x005E, 0)
+            .unwrap_err()
+            .is_invalid_date());
+        // 62 seconds
+        assert!(HttpDate::from_dos_datetime(0x0021, 0x001F).is_err());
+        // 24 hours
+        assert!(HttpDate::from_dos_datetime(0x0021, 0xC000).is_err());
+
+        assert!(HttpDate::EPOCH.to_dos_datetime().is_err());
+        let after: HttpDate = "Sun, 01 Jan 2108 00:00:00 GMT".parse().unwrap();
+        assert!(after.to_dos_datetime().unwrap_err().is_out_of_range());
+    }
+}
@@ -0,0 +1,187 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::{error, io};
+
+/// Number of input bytes copied into a detailed error.
+#[cfg(feature = "detailed-errors")]
+const INPUT_LEN: usize = 40;
+
+/// The reason a date was rejected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ErrorKind {
+    /// The input does not match any of the supported formats.
+    Format,
+    /// A field is outside of its allowed range.
+    Range,
+    /// The fields do not describe a real date, e.g. the weekday is wrong.
+    Calendar,
+    /// The platform or file system does not provide the time.
+    Unavailable,
+}
+
+impl ErrorKind {
+    #[cfg(feature = "detailed-errors")]
+    fn description(self) -> &'static str {
+        match self {
+            ErrorKind::Format => "unrecognized format",
+            ErrorKind::Range => "value out of range",
+            ErrorKind::Calendar => "no such date",
+            ErrorKind::Unavailable => "time not available",
+        }
+    }
+}
+
+#[cfg(not(feature = "detailed-errors"))]
+type Repr = ErrorKind;
+
+#[cfg(feature = "detailed-errors")]
+type Repr = Details;
+
+#[cfg(feature = "detailed-errors")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Details {
+    kind: ErrorKind,
+    offset: Option<usize>,
+    input: [u8; INPUT_LEN],
+    input_len: u8,
+}
+
+/// An opaque error type for all parsing errors.
+///
+/// Use the `is_*` methods to find out why a date was rejected.
+///
+/// The error is a single byte by default that stores the reason for the
+/// `is_*` methods. Earlier versions had a zero-sized error, a
+/// `Result<HttpDate, Error>` still takes 16 bytes. With the
+/// `detailed-errors` feature it also records the byte offset of the
+/// problem and a copy of the start of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(Repr);
+
+impl Error {
+    #[cfg(not(feature = "detailed-errors"))]
+    #[inline]
+    pub(crate) fn new(kind: ErrorKind, _offset: Option<usize>) -> Error {
+        Error(kind)
+    }
+
+    #[cfg(feature = "detailed-errors")]
+    pub(crate) fn new(kind: ErrorKind, offset: Option<usize>) -> Error {
+        Error(Details {
+            kind,
+            offset,
+            input: [0; INPUT_LEN],
+            input_len: 0,
+        })
+    }
+
+    /// A syntax error at the given offset.
+    #[inline]
+    pub(crate) fn format(offset: usize) -> Error {
+        Error::new(ErrorKind::Format, Some(offset))
+    }
+
+    #[cfg(not(feature = "detailed-errors"))]
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+
+    #[cfg(feature = "detailed-errors")]
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        self.0.kind
+    }
+
+    /// The input does not match any of the supported date formats.
+    pub fn is_unrecognized_format(&self) -> bool {
+        self.kind() == ErrorKind::Format
+    }
+
+    /// The input is well-formed but a field is out of range,
+    /// for example the hour `25` or a year before 1970.
+    pub fn is_out_of_range(&self) -> bool {
+        self.kind() == ErrorKind::Range
+    }
+
+    /// The fields are in range but do not describe a real date,
+    /// for example February 30 or a wrong weekday.
+    pub fn is_invalid_date(&self) -> bool {
+        self.kind() == ErrorKind::Calendar
+    }
+
+    /// The time could not be read, for example because the file system
+    /// does not record modification times.
+    pub fn is_unavailable(&self) -> bool {
+        self.kind() == ErrorKind::Unavailable
+    }
+
+    /// Attach the input and move the offset by `base` bytes.
+    #[cfg(not(feature = "detailed-errors"))]
+    #[inline]
+    pub(crate) fn with_input(self, _input: &[u8], _base: usize) -> Error {
+        self
+    }
+
+    #[cfg(feature = "detailed-errors")]
+    pub(crate) fn with_input(mut self, input: &[u8], base: usize) -> Error {
+        let len = input.len().min(INPUT_LEN);
+        self.0.input[..len].copy_from_slice(&input[..len]);
+        self.0.input_len = len as u8;
+        self.0.offset = self.0.offset.map(|o| o + base);
+        self
+    }
+
+    /// Byte offset of the problem in the input, if it is known.
+    ///
+    /// Only available with the `detailed-errors` feature.
+    #[cfg(feature = "detailed-errors")]
+    pub fn offset(&self) -> Option<usize> {
+        self.0.offset
+    }
+
+    /// The start of the rejected input, up to 40 bytes.
+    ///
+    /// Only available with the `detailed-errors` feature.
+    #[cfg(feature = "detailed-errors")]
+    pub fn input(&self) -> &[u8] {
+        &self.0.input[..self.0.input_len as usize]
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("string contains no or an invalid date")?;
+        #[cfg(feature = "detailed-errors")]
+        {
+            write!(f, ": {}", self.0.kind.description())?;
+            if let Some(offset) = self.0.offset {
+                write!(f, " at byte {}", offset)?;
+            }
+            if !self.input().is_empty() {
+                f.write_str(" in \"")?;
+                for &b in self.input() {
+                    if b == b'"' || b == b'\\' {
+                        write!(f, "\\{}", b as char)?;
+                    } else if b == b' ' || b.is_ascii_graphic() {
+                        write!(f, "{}", b as char)?;
+                    } else {
+                        write!(f, "\\x{:02x}", b)?;
+                    }
+                }
+                f.write_str("\"")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for io::Error {
+    fn from(e: Error) -> io::Error {
+        io::Error::other(e)
+    }
+}
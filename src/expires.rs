@@ -0,0 +1,223 @@
+//! Generate `Expires` dates and matching `max-age` values, and check that
+//! they agree.
+
+use core::time::Duration;
+
+use crate::date::MAX_SECS;
+use crate::HttpDate;
+
+const DAY: u64 = 86400;
+
+/// One year, the longest lifetime senders should use for "never expires".
+const YEAR: u64 = 365 * DAY;
+
+/// How long a response may be cached.
+///
+/// Use [`ExpiresPolicy::expires`] to get the `Expires` header field and
+/// [`ExpiresPolicy::max_age`] for the matching `max-age` directive. All
+/// dates are relative to the `Date` of the response.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::{expires::ExpiresPolicy, HttpDate};
+/// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let policy = ExpiresPolicy::For(Duration::from_secs(3600));
+/// assert_eq!(policy.expires(date).to_string(), "Sun, 06 Nov 1994 09:49:37 GMT");
+/// assert_eq!(policy.max_age(date), Duration::from_secs(3600));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ExpiresPolicy {
+    /// The response is stale immediately.
+    Immediately,
+    /// The response is fresh for this long, in whole seconds.
+    For(Duration),
+    /// The response is fresh until the next midnight UTC.
+    EndOfDay,
+    /// The response never changes, it is fresh for one year.
+    Never,
+}
+
+impl ExpiresPolicy {
+    /// The `Expires` date for a response generated at `date`.
+    ///
+    /// Dates after the year 9999 are clamped.
+    pub fn expires(&self, date: HttpDate) -> HttpDate {
+        let secs = date.secs_since_epoch();
+        let expires = secs
+            .saturating_add(self.lifetime_secs(secs))
+            .min(MAX_SECS - 1);
+        HttpDate::from_secs_since_epoch(expires).expect("clamped to valid range")
+    }
+
+    /// The `max-age` directive matching [`ExpiresPolicy::expires`].
+    pub fn max_age(&self, date: HttpDate) -> Duration {
+        let secs = date.secs_since_epoch();
+        Duration::from_secs(self.expires(date).secs_since_epoch() - secs)
+    }
+
+    fn lifetime_secs(&self, secs: u64) -> u64 {
+        match *self {
+            ExpiresPolicy::Immediately => 0,
+            ExpiresPolicy::For(duration) => duration.as_secs(),
+            ExpiresPolicy::EndOfDay => DAY - secs % DAY,
+            ExpiresPolicy::Never => YEAR,
+        }
+    }
+}
+
+/// A disagreement between the `Date`, `Expires` and `max-age` of a
+/// response, found by [`check_consistency`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Inconsistency {
+    /// `Expires` is before `Date`, the response is stale when it is sent.
+    ExpiresBeforeDate,
+    /// The lifetime given by `Expires` and `max-age` differ by more than
+    /// the tolerance. Caches that understand `max-age` ignore `Expires`,
+    /// so HTTP/1.0 caches will use a different lifetime.
+    MaxAgeMismatch {
+        /// The time from `Date` to `Expires`, zero if it is before.
+        expires_lifetime: Duration,
+        /// The `max-age` directive.
+        max_age: Duration,
+    },
+}
+
+/// Report inconsistencies between the `Date` and `Expires` header fields
+/// and the `max-age` directive of a response, for example to flag
+/// misconfigured origin servers.
+///
+/// The lifetimes may differ by up to `tolerance` to allow for the time
+/// between generating the headers and sending the response.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::{expires::{check_consistency, Inconsistency}, HttpDate};
+/// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let expires: HttpDate = "Sun, 06 Nov 1994 09:49:37 GMT".parse().unwrap();
+/// let tolerance = Duration::from_secs(5);
+/// let max_age = Some(Duration::from_secs(3600));
+/// assert_eq!(check_consistency(date, expires, max_age, tolerance).count(), 0);
+/// let max_age = Some(Duration::from_secs(60));
+/// let problems: Vec<_> = check_consistency(date, expires, max_age, tolerance).collect();
+/// assert_eq!(
+///     problems,
+///     [Inconsistency::MaxAgeMismatch {
+///         expires_lifetime: Duration::from_secs(3600),
+///         max_age: Duration::from_secs(60),
+///     }]
+/// );
+/// ```
+pub fn check_consistency(
+    date: HttpDate,
+    expires: HttpDate,
+    max_age: Option<Duration>,
+    tolerance: Duration,
+) -> impl Iterator<Item = Inconsistency> {
+    let before = if expires < date {
+        Some(Inconsistency::ExpiresBeforeDate)
+    } else {
+        None
+    };
+    let expires_lifetime = Duration::from_secs(
+        expires
+            .secs_since_epoch()
+            .saturating_sub(date.secs_since_epoch()),
+    );
+    let mismatch = max_age.and_then(|max_age| {
+        let difference = if expires_lifetime > max_age {
+            expires_lifetime - max_age
+        } else {
+            max_age - expires_lifetime
+        };
+        if difference > tolerance {
+            Some(Inconsistency::MaxAgeMismatch {
+                expires_lifetime,
+                max_age,
+            })
+        } else {
+            None
+        }
+    });
+    [before, mismatch].into_iter().flatten()
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{check_consistency, ExpiresPolicy, Inconsistency};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_expires_policy() {
+        let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let expires = |p: ExpiresPolicy| p.expires(date).to_string();
+        assert_eq!(
+            expires(ExpiresPolicy::Immediately),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+        assert_eq!(
+            expires(ExpiresPolicy::EndOfDay),
+            "Mon, 07 Nov 1994 00:00:00 GMT"
+        );
+        assert_eq!(
+            expires(ExpiresPolicy::Never),
+            "Mon, 06 Nov 1995 08:49:37 GMT"
+        );
+        assert_eq!(
+            ExpiresPolicy::Immediately.max_age(date),
+            Duration::from_secs(0)
+        );
+        assert_eq!(
+            ExpiresPolicy::EndOfDay.max_age(date),
+            Duration::from_secs(15 * 3600 + 10 * 60 + 23)
+        );
+
+        let midnight = HttpDate::from(UNIX_EPOCH + Duration::from_secs(86400));
+        assert_eq!(
+            ExpiresPolicy::EndOfDay.max_age(midnight),
+            Duration::from_secs(86400)
+        );
+        let forever = ExpiresPolicy::For(Duration::from_secs(u64::MAX));
+        assert_eq!(
+            forever.expires(date).to_string(),
+            "Fri, 31 Dec 9999 23:59:59 GMT"
+        );
+    }
+
+    #[test]
+    fn test_check_consistency() {
+        let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let check = |expires: HttpDate, max_age: Option<u64>| {
+            let max_age = max_age.map(Duration::from_secs);
+            check_consistency(date, expires, max_age, Duration::from_secs(2)).collect::<Vec<_>>()
+        };
+        let policy = ExpiresPolicy::For(Duration::from_secs(600));
+        assert_eq!(check(policy.expires(date), None), []);
+        assert_eq!(check(policy.expires(date), Some(598)), []);
+        assert_eq!(check(policy.expires(date), Some(602)), []);
+        assert_eq!(
+            check(policy.expires(date), Some(603)),
+            [Inconsistency::MaxAgeMismatch {
+                expires_lifetime: Duration::from_secs(600),
+                max_age: Duration::from_secs(603),
+            }]
+        );
+        assert_eq!(
+            check(HttpDate::EXPIRED, Some(0)),
+            [Inconsistency::ExpiresBeforeDate]
+        );
+        assert_eq!(
+            check(HttpDate::EXPIRED, Some(60)),
+            [
+                Inconsistency::ExpiresBeforeDate,
+                Inconsistency::MaxAgeMismatch {
+                    expires_lifetime: Duration::from_secs(0),
+                    max_age: Duration::from_secs(60),
+                }
+            ]
+        );
+        assert_eq!(check(date, Some(0)), []);
+    }
+}
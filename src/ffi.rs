@@ -0,0 +1,92 @@
+//! C interface, enabled with the `ffi` feature.
+//!
+//! The functions use the C calling convention and unmangled names so
+//! `cbindgen` can generate a header for them. Build a static library with
+//! `cargo rustc --release --features ffi --crate-type staticlib`.
+//!
+//! ```c
+//! int httpdate_parse(const char *s, int64_t *out);
+//! int httpdate_format(int64_t secs, char out[30]);
+//! ```
+#![allow(unsafe_code)]
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+
+use crate::{HttpDate, IMF_FIXDATE_LEN};
+
+/// Parse a NUL-terminated HTTP date into seconds since the Unix epoch.
+///
+/// Returns `0` on success and stores the result in `out`.
+/// Returns `-1` if the input is not a valid date or a pointer is null.
+///
+/// # Safety
+///
+/// `s` must point to a NUL-terminated string and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn httpdate_parse(s: *const c_char, out: *mut i64) -> c_int {
+    if s.is_null() || out.is_null() {
+        return -1;
+    }
+    match HttpDate::parse_bytes(CStr::from_ptr(s).to_bytes()) {
+        Ok(d) => {
+            *out = d.secs_since_epoch() as i64;
+            0
+        }
+        Err(_) => -1,
+    }
+}
+
+/// Format seconds since the Unix epoch as IMF-fixdate.
+///
+/// Returns `0` on success and writes 29 characters and a terminating NUL to `out`.
+/// Returns `-1` if the time is before 1970 or after 9999 or `out` is null.
+///
+/// # Safety
+///
+/// `out` must be valid for writes of 30 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn httpdate_format(secs: i64, out: *mut c_char) -> c_int {
+    if out.is_null() || secs < 0 {
+        return -1;
+    }
+    let d = match HttpDate::from_secs_since_epoch(secs as u64) {
+        Some(d) => d,
+        None => return -1,
+    };
+    let out = &mut *(out as *mut [u8; 30]);
+    let mut buf = [0; IMF_FIXDATE_LEN];
+    out[..IMF_FIXDATE_LEN].copy_from_slice(d.format_into(&mut buf));
+    out[IMF_FIXDATE_LEN] = 0;
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use std::ptr;
+
+    use super::{httpdate_format, httpdate_parse};
+
+    #[test]
+    fn test_parse() {
+        let mut out = 0;
+        let s = b"Sun, 06 Nov 1994 08:49:37 GMT\0";
+        assert_eq!(unsafe { httpdate_parse(s.as_ptr().cast(), &mut out) }, 0);
+        assert_eq!(out, 784111777);
+        let s = b"Sun, 06 Nov 1994\0";
+        assert_eq!(unsafe { httpdate_parse(s.as_ptr().cast(), &mut out) }, -1);
+        assert_eq!(unsafe { httpdate_parse(ptr::null(), &mut out) }, -1);
+    }
+
+    #[test]
+    fn test_format() {
+        let mut out = [1; 30];
+        assert_eq!(unsafe { httpdate_format(784111777, out.as_mut_ptr()) }, 0);
+        let s = unsafe { CStr::from_ptr(out.as_ptr()) };
+        assert_eq!(s.to_str(), Ok("Sun, 06 Nov 1994 08:49:37 GMT"));
+        assert_eq!(unsafe { httpdate_format(-1, out.as_mut_ptr()) }, -1);
+        assert_eq!(unsafe { httpdate_format(i64::MAX, out.as_mut_ptr()) }, -1);
+        assert_eq!(unsafe { httpdate_format(0, ptr::null_mut()) }, -1);
+    }
+}
@@ -0,0 +1,68 @@
+//! Conversions from and to Windows `FILETIME` values, enabled with the
+//! `filetime` feature.
+
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+/// `FILETIME` intervals per second.
+const INTERVALS_PER_SEC: u64 = 10_000_000;
+
+/// Seconds from 1601-01-01 to 1970-01-01.
+const EPOCH_DIFF_SECS: u64 = 11_644_473_600;
+
+impl HttpDate {
+    /// Convert a `FILETIME`, the number of 100 nanosecond intervals since
+    /// 1601-01-01T00:00:00Z, truncating it to whole seconds.
+    ///
+    /// Returns an out of range error for times before 1970 or after 9999.
+    /// Only available with the `filetime` feature.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::from_filetime(124_285_853_770_000_000).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// assert_eq!(date.to_filetime(), 124_285_853_770_000_000);
+    /// ```
+    pub fn from_filetime(filetime: u64) -> Result<HttpDate, Error> {
+        (filetime / INTERVALS_PER_SEC)
+            .checked_sub(EPOCH_DIFF_SECS)
+            .and_then(HttpDate::from_secs_since_epoch)
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+
+    /// The date as a `FILETIME`, the number of 100 nanosecond intervals
+    /// since 1601-01-01T00:00:00Z.
+    ///
+    /// Only available with the `filetime` feature.
+    pub fn to_filetime(&self) -> u64 {
+        (self.secs_since_epoch() + EPOCH_DIFF_SECS) * INTERVALS_PER_SEC
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use crate::HttpDate;
+
+    #[test]
+    fn test_filetime() {
+        assert_eq!(
+            HttpDate::from_filetime(116_444_736_000_000_000).unwrap(),
+            HttpDate::EPOCH
+        );
+        assert_eq!(HttpDate::EPOCH.to_filetime(), 116_444_736_000_000_000);
+        assert_eq!(
+            HttpDate::from_filetime(116_444_736_009_999_999).unwrap(),
+            HttpDate::EPOCH
+        );
+        assert!(HttpDate::from_filetime(116_444_735_999_999_999)
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(HttpDate::from_filetime(0).is_err());
+        let max = HttpDate::from_filetime(2_650_467_743_990_000_000).unwrap();
+        assert_eq!(max.to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+        assert_eq!(max.to_filetime(), 2_650_467_743_990_000_000);
+        assert!(HttpDate::from_filetime(2_650_467_744_000_000_000).is_err());
+        assert!(HttpDate::from_filetime(u64::MAX).is_err());
+    }
+}
@@ -0,0 +1,398 @@
+//! Cache freshness calculations from RFC 9111.
+
+use std::time::{Duration, SystemTime};
+
+use crate::clock::Clock;
+use crate::{Age, HttpDate};
+
+/// Duration from `earlier` to `later`, zero if `later` is before `earlier`.
+fn saturating_since(later: SystemTime, earlier: SystemTime) -> Duration {
+    later
+        .duration_since(earlier)
+        .unwrap_or(Duration::from_secs(0))
+}
+
+/// Calculate the current age of a cached response, see RFC 9111 section 4.2.3.
+///
+/// * `date` is the `Date` header field of the response
+/// * `age` is the `Age` header field of the response, if present
+/// * `request_time` is the local time the request was sent
+/// * `response_time` is the local time the response was received
+/// * `now` is the current local time
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use httpdate::{freshness::current_age, Age, HttpDate};
+/// let request_time = UNIX_EPOCH + Duration::from_secs(784111777);
+/// let response_time = request_time + Duration::from_secs(2);
+/// let date = HttpDate::from(request_time);
+/// let now = response_time + Duration::from_secs(60);
+/// let age = current_age(date, Some(Age::from_secs(10)), request_time, response_time, now);
+/// assert_eq!(age, Duration::from_secs(72));
+/// ```
+pub fn current_age(
+    date: HttpDate,
+    age: Option<Age>,
+    request_time: SystemTime,
+    response_time: SystemTime,
+    now: SystemTime,
+) -> Duration {
+    let apparent_age = saturating_since(response_time, date.into());
+    let response_delay = saturating_since(response_time, request_time);
+    let age_value = age.map_or(Duration::from_secs(0), Duration::from);
+    let corrected_age_value = age_value + response_delay;
+    let corrected_initial_age = apparent_age.max(corrected_age_value);
+    let resident_time = saturating_since(now, response_time);
+    corrected_initial_age + resident_time
+}
+
+/// Local times a request was sent and its response received.
+///
+/// Capture `request_time` right before sending the request, including
+/// any time spent in a proxy, and `response_time` as soon as the response
+/// headers were received, see RFC 9111 section 4.2.3.
+///
+/// ```
+/// # use httpdate::{freshness::Timings, HttpDate};
+/// let mut timings = Timings::start();
+/// // Send the request and wait for the response ...
+/// timings.received();
+/// let age = timings.current_age(HttpDate::now(), None, timings.response_time);
+/// assert!(age.as_secs() <= 1);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Timings {
+    /// Local time the request was sent.
+    pub request_time: SystemTime,
+    /// Local time the response was received.
+    pub response_time: SystemTime,
+}
+
+impl Timings {
+    /// Capture the current time as the time the request is sent.
+    ///
+    /// The response time is set to the same time until
+    /// [`Timings::received`] is called.
+    pub fn start() -> Timings {
+        let now = SystemTime::now();
+        Timings {
+            request_time: now,
+            response_time: now,
+        }
+    }
+
+    /// Capture the current time as the time the response was received.
+    pub fn received(&mut self) {
+        self.response_time = SystemTime::now();
+    }
+
+    /// The current age of the response, see [`current_age`].
+    pub fn current_age(&self, date: HttpDate, age: Option<Age>, now: SystemTime) -> Duration {
+        current_age(date, age, self.request_time, self.response_time, now)
+    }
+}
+
+/// Calculate the freshness lifetime of a response, see RFC 9111 section 4.2.1.
+///
+/// The first available of these is used:
+///
+/// 1. `s_maxage`, the `s-maxage` directive, only pass it for shared caches
+/// 2. `max_age`, the `max-age` directive
+/// 3. the difference between `expires` and `date`, the `Expires` and
+///    `Date` header fields
+///
+/// Returns `None` if there is no explicit lifetime, a cache may then use
+/// a heuristic. An `Expires` date before `Date` gives a lifetime of zero.
+/// If the `Expires` header field is present but invalid the response is
+/// already stale: pass `Some(HttpDate::from(UNIX_EPOCH))` or treat the
+/// lifetime as zero.
+pub fn freshness_lifetime(
+    date: HttpDate,
+    expires: Option<HttpDate>,
+    max_age: Option<Duration>,
+    s_maxage: Option<Duration>,
+) -> Option<Duration> {
+    s_maxage
+        .or(max_age)
+        .or_else(|| expires.map(|expires| saturating_since(expires.into(), date.into())))
+}
+
+/// Calculate a heuristic freshness lifetime, see RFC 9111 section 4.2.2.
+///
+/// Uses 10% of the time between `last_modified` and `date`, limited to
+/// `cap`. Only use this for responses without an explicit lifetime.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::{freshness::heuristic_freshness, HttpDate};
+/// let date: HttpDate = "Sun, 11 Oct 2020 00:00:00 GMT".parse().unwrap();
+/// let last_modified: HttpDate = "Thu, 01 Oct 2020 00:00:00 GMT".parse().unwrap();
+/// let cap = Duration::from_secs(7 * 86400);
+/// assert_eq!(heuristic_freshness(date, last_modified, cap), Duration::from_secs(86400));
+/// ```
+pub fn heuristic_freshness(date: HttpDate, last_modified: HttpDate, cap: Duration) -> Duration {
+    let age = saturating_since(date.into(), last_modified.into());
+    (age / 10).min(cap)
+}
+
+/// Everything needed to decide if a cached response is fresh.
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use httpdate::{freshness::FreshnessInputs, HttpDate};
+/// let request_time = UNIX_EPOCH + Duration::from_secs(784111777);
+/// let response_time = request_time + Duration::from_secs(1);
+/// let inputs = FreshnessInputs {
+///     date: HttpDate::from(request_time),
+///     age: None,
+///     expires: None,
+///     max_age: Some(Duration::from_secs(60)),
+///     s_maxage: None,
+///     request_time,
+///     response_time,
+/// };
+/// assert!(inputs.is_fresh(response_time + Duration::from_secs(30)));
+/// assert!(!inputs.is_fresh(response_time + Duration::from_secs(60)));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FreshnessInputs {
+    /// `Date` header field of the response.
+    pub date: HttpDate,
+    /// `Age` header field of the response.
+    pub age: Option<Age>,
+    /// `Expires` header field of the response.
+    pub expires: Option<HttpDate>,
+    /// `max-age` cache directive of the response.
+    pub max_age: Option<Duration>,
+    /// `s-maxage` cache directive of the response, only set it for shared caches.
+    pub s_maxage: Option<Duration>,
+    /// Local time the request was sent.
+    pub request_time: SystemTime,
+    /// Local time the response was received.
+    pub response_time: SystemTime,
+}
+
+impl FreshnessInputs {
+    /// The explicit freshness lifetime, see [`freshness_lifetime`].
+    pub fn freshness_lifetime(&self) -> Option<Duration> {
+        freshness_lifetime(self.date, self.expires, self.max_age, self.s_maxage)
+    }
+
+    /// The current age of the response, see [`current_age`].
+    pub fn current_age(&self, now: SystemTime) -> Duration {
+        current_age(
+            self.date,
+            self.age,
+            self.request_time,
+            self.response_time,
+            now,
+        )
+    }
+
+    /// Whether the response can be served from the cache at `now`.
+    ///
+    /// A response without an explicit freshness lifetime is never fresh,
+    /// use [`heuristic_freshness`] to handle those.
+    pub fn is_fresh(&self, now: SystemTime) -> bool {
+        match self.freshness_lifetime() {
+            Some(lifetime) => lifetime > self.current_age(now),
+            None => false,
+        }
+    }
+
+    /// Whether the response can be served from the cache at the time of
+    /// `clock`, see [`FreshnessInputs::is_fresh`].
+    pub fn is_fresh_with<C: Clock>(&self, clock: &C) -> bool {
+        self.is_fresh(clock.now().into())
+    }
+}
+
+/// The interval in which a response is fresh.
+///
+/// The window starts at `from` and ends before `until`.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::{freshness::FreshnessWindow, HttpDate};
+/// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let expires: HttpDate = "Sun, 06 Nov 1994 09:49:37 GMT".parse().unwrap();
+/// let window = FreshnessWindow { from: date, until: expires };
+/// assert!(window.contains(date));
+/// assert!(!window.contains(expires));
+/// assert_eq!(window.remaining(date), Duration::from_secs(3600));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FreshnessWindow {
+    /// First second in which the response is fresh.
+    pub from: HttpDate,
+    /// First second in which the response is stale.
+    pub until: HttpDate,
+}
+
+impl FreshnessWindow {
+    /// Whether the window contains no date at all.
+    pub fn is_empty(&self) -> bool {
+        self.from >= self.until
+    }
+
+    /// Whether the response is fresh at `date`.
+    pub fn contains(&self, date: HttpDate) -> bool {
+        self.from <= date && date < self.until
+    }
+
+    /// Whether the response is fresh at the time of `clock`.
+    pub fn is_fresh_with<C: Clock>(&self, clock: &C) -> bool {
+        self.contains(clock.now())
+    }
+
+    /// Time from `now` until the response becomes stale, zero if it is
+    /// already stale.
+    pub fn remaining(&self, now: HttpDate) -> Duration {
+        saturating_since(self.until.into(), now.into())
+    }
+
+    /// The dates in which both windows are fresh.
+    ///
+    /// Returns `None` if the windows do not overlap.
+    pub fn intersection(&self, other: &FreshnessWindow) -> Option<FreshnessWindow> {
+        let window = FreshnessWindow {
+            from: self.from.max(other.from),
+            until: self.until.min(other.until),
+        };
+        if window.is_empty() {
+            None
+        } else {
+            Some(window)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::{
+        current_age, freshness_lifetime, heuristic_freshness, FreshnessInputs, FreshnessWindow,
+        Timings,
+    };
+    use crate::clock::MockClock;
+    use crate::{Age, HttpDate};
+
+    fn t(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_000_000 + secs)
+    }
+
+    #[test]
+    fn test_current_age() {
+        // The apparent age dominates when the origin clock is behind.
+        let age = current_age(HttpDate::from(t(0)), None, t(100), t(101), t(110));
+        assert_eq!(age, Duration::from_secs(110));
+        // The Age value dominates, corrected by the response delay.
+        let age = current_age(
+            HttpDate::from(t(100)),
+            Some(Age::from_secs(30)),
+            t(100),
+            t(103),
+            t(103),
+        );
+        assert_eq!(age, Duration::from_secs(33));
+        // A Date in the future gives no negative apparent age.
+        let age = current_age(HttpDate::from(t(500)), None, t(100), t(100), t(105));
+        assert_eq!(age, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_freshness_lifetime() {
+        let date = HttpDate::from(t(0));
+        let expires = Some(HttpDate::from(t(600)));
+        let secs = |s| Some(Duration::from_secs(s));
+        assert_eq!(freshness_lifetime(date, None, None, None), None);
+        assert_eq!(freshness_lifetime(date, expires, None, None), secs(600));
+        assert_eq!(freshness_lifetime(date, expires, secs(60), None), secs(60));
+        assert_eq!(
+            freshness_lifetime(date, expires, secs(60), secs(30)),
+            secs(30)
+        );
+        assert_eq!(freshness_lifetime(date, None, None, secs(0)), secs(0));
+        let past = Some(HttpDate::from(UNIX_EPOCH));
+        assert_eq!(freshness_lifetime(date, past, None, None), secs(0));
+    }
+
+    #[test]
+    fn test_heuristic_freshness() {
+        let cap = Duration::from_secs(3600);
+        let date = HttpDate::from(t(10_000));
+        let h = |lm| heuristic_freshness(date, HttpDate::from(t(lm)), cap);
+        assert_eq!(h(9_000), Duration::from_secs(100));
+        assert_eq!(h(0), Duration::from_secs(1000));
+        assert_eq!(h(10_000), Duration::from_secs(0));
+        assert_eq!(h(20_000), Duration::from_secs(0));
+        let date = HttpDate::from(t(100_000));
+        assert_eq!(heuristic_freshness(date, HttpDate::from(t(0)), cap), cap);
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let mut inputs = FreshnessInputs {
+            date: HttpDate::from(t(0)),
+            age: Some(Age::from_secs(100)),
+            expires: Some(HttpDate::from(t(300))),
+            max_age: None,
+            s_maxage: None,
+            request_time: t(0),
+            response_time: t(0),
+        };
+        assert_eq!(inputs.freshness_lifetime(), Some(Duration::from_secs(300)));
+        assert_eq!(inputs.current_age(t(50)), Duration::from_secs(150));
+        assert!(inputs.is_fresh(t(199)));
+        assert!(!inputs.is_fresh(t(200)));
+        let clock = MockClock::new(HttpDate::from(t(199)));
+        assert!(inputs.is_fresh_with(&clock));
+        clock.advance(Duration::from_secs(1));
+        assert!(!inputs.is_fresh_with(&clock));
+        inputs.max_age = Some(Duration::from_secs(120));
+        assert!(!inputs.is_fresh(t(50)));
+        inputs.expires = None;
+        inputs.max_age = None;
+        assert!(!inputs.is_fresh(t(0)));
+    }
+
+    #[test]
+    fn test_freshness_window() {
+        let w = |from, until| FreshnessWindow {
+            from: HttpDate::from(t(from)),
+            until: HttpDate::from(t(until)),
+        };
+        let window = w(100, 200);
+        assert!(!window.contains(HttpDate::from(t(99))));
+        assert!(window.contains(HttpDate::from(t(199))));
+        assert!(window.is_fresh_with(&MockClock::new(HttpDate::from(t(100)))));
+        assert_eq!(
+            window.remaining(HttpDate::from(t(150))),
+            Duration::from_secs(50)
+        );
+        assert_eq!(
+            window.remaining(HttpDate::from(t(250))),
+            Duration::from_secs(0)
+        );
+        assert_eq!(window.intersection(&w(150, 300)), Some(w(150, 200)));
+        assert_eq!(window.intersection(&w(120, 130)), Some(w(120, 130)));
+        assert_eq!(window.intersection(&w(200, 300)), None);
+        assert!(w(100, 100).is_empty());
+    }
+
+    #[test]
+    fn test_timings() {
+        let timings = Timings {
+            request_time: t(100),
+            response_time: t(103),
+        };
+        let age = timings.current_age(HttpDate::from(t(100)), Some(Age::from_secs(30)), t(103));
+        assert_eq!(age, Duration::from_secs(33));
+
+        let mut timings = Timings::start();
+        assert_eq!(timings.request_time, timings.response_time);
+        timings.received();
+        assert!(timings.response_time >= timings.request_time);
+    }
+}
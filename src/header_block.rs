@@ -0,0 +1,97 @@
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+/// Maximum number of header fields `DateHeaders::parse_block` accepts.
+const MAX_HEADERS: usize = 64;
+
+/// The date header fields of a message, found in a single pass.
+///
+/// Each field is `None` if it is missing and `Some(Err(_))` if it
+/// is not a valid date or occurs more than once.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DateHeaders {
+    /// `Date` header field.
+    pub date: Option<Result<HttpDate, Error>>,
+    /// `If-Modified-Since` header field.
+    pub if_modified_since: Option<Result<HttpDate, Error>>,
+    /// `Last-Modified` header field.
+    pub last_modified: Option<Result<HttpDate, Error>>,
+}
+
+impl DateHeaders {
+    /// Find and parse the date fields in headers parsed by `httparse`.
+    ///
+    /// The values are parsed as bytes, no UTF-8 conversion is needed.
+    pub fn from_httparse(headers: &[httparse::Header<'_>]) -> DateHeaders {
+        let mut dates = DateHeaders::default();
+        for header in headers {
+            let field = if header.name.eq_ignore_ascii_case("date") {
+                &mut dates.date
+            } else if header.name.eq_ignore_ascii_case("if-modified-since") {
+                &mut dates.if_modified_since
+            } else if header.name.eq_ignore_ascii_case("last-modified") {
+                &mut dates.last_modified
+            } else {
+                continue;
+            };
+            *field = Some(match field {
+                None => HttpDate::parse_bytes(header.value),
+                Some(_) => Err(Error::new(ErrorKind::Format, None)),
+            });
+        }
+        dates
+    }
+
+    /// Parse a raw header block terminated by an empty line and find the
+    /// date fields in it.
+    ///
+    /// Returns `Status::Partial` if the block is incomplete.
+    /// At most 64 header fields are supported.
+    pub fn parse_block(block: &[u8]) -> httparse::Result<DateHeaders> {
+        let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+        Ok(match httparse::parse_headers(block, &mut headers)? {
+            httparse::Status::Complete((_, headers)) => {
+                httparse::Status::Complete(DateHeaders::from_httparse(headers))
+            }
+            httparse::Status::Partial => httparse::Status::Partial,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateHeaders;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_from_httparse() {
+        let mut headers = [httparse::EMPTY_HEADER; 8];
+        let mut req = httparse::Request::new(&mut headers);
+        let buf = b"GET / HTTP/1.1\r\n\
+            Host: example.com\r\n\
+            if-modified-since: Sun, 06 Nov 1994 08:49:37 GMT\r\n\
+            Date: Sun, 06 Nov 1994 08:49:37 \xff\r\n\r\n";
+        assert!(req.parse(buf).unwrap().is_complete());
+        let dates = DateHeaders::from_httparse(req.headers);
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert_eq!(dates.if_modified_since, Some(Ok(d)));
+        assert!(matches!(dates.date, Some(Err(_))));
+        assert_eq!(dates.last_modified, None);
+    }
+
+    #[test]
+    fn test_parse_block() {
+        let status = DateHeaders::parse_block(
+            b"Last-Modified: Sun, 06 Nov 1994 08:49:37 GMT\r\n\
+            Last-Modified: Sun, 06 Nov 1994 08:49:37 GMT\r\n\
+            Date: Sunday, 06-Nov-94 08:49:37 GMT\r\n\r\n",
+        )
+        .unwrap();
+        let dates = status.unwrap();
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert_eq!(dates.date, Some(Ok(d)));
+        assert!(matches!(dates.last_modified, Some(Err(_))));
+        assert!(DateHeaders::parse_block(b"Date: Sun").unwrap().is_partial());
+        assert!(DateHeaders::parse_block(b"Date\0: Sun\r\n\r\n").is_err());
+    }
+}
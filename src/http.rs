@@ -0,0 +1,85 @@
+//! Conversions from and to `http::HeaderValue`, enabled with the `http` feature.
+
+use std::convert::TryFrom;
+
+use ::http::header::AsHeaderName;
+use ::http::{HeaderMap, HeaderValue};
+
+use crate::error::ErrorKind;
+use crate::{Error, HttpDate, IMF_FIXDATE_LEN};
+
+/// Get and parse a header field containing a single HTTP date.
+///
+/// Returns `None` if the field is missing and an error if the value is not
+/// a valid date or the field occurs more than once. Values are parsed as
+/// bytes so non-UTF-8 values are simply rejected.
+///
+/// ```
+/// # use http::header::{HeaderMap, HeaderValue, LAST_MODIFIED};
+/// let mut headers = HeaderMap::new();
+/// headers.insert(LAST_MODIFIED, HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// let last_modified = httpdate::get_date(&headers, LAST_MODIFIED);
+/// assert!(matches!(last_modified, Some(Ok(_))));
+/// ```
+pub fn get_date<K: AsHeaderName>(headers: &HeaderMap, key: K) -> Option<Result<HttpDate, Error>> {
+    let mut values = headers.get_all(key).iter();
+    let value = values.next()?;
+    if values.next().is_some() {
+        return Some(Err(Error::new(ErrorKind::Format, None)));
+    }
+    Some(HttpDate::parse_bytes(value.as_bytes()))
+}
+
+impl TryFrom<&HeaderValue> for HttpDate {
+    type Error = Error;
+
+    /// Parse the raw bytes of a header value without converting them to `&str`.
+    fn try_from(v: &HeaderValue) -> Result<HttpDate, Error> {
+        HttpDate::parse_bytes(v.as_bytes())
+    }
+}
+
+impl From<HttpDate> for HeaderValue {
+    fn from(v: HttpDate) -> HeaderValue {
+        let mut buf = [0; IMF_FIXDATE_LEN];
+        HeaderValue::from_bytes(v.format_into(&mut buf))
+            .expect("IMF-fixdate is a valid header value")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::http::header::{HeaderMap, HeaderValue, DATE, EXPIRES};
+
+    use super::get_date;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_header_value() {
+        let v = HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT");
+        let d = HttpDate::try_from(&v).unwrap();
+        assert_eq!(HeaderValue::from(d), v);
+
+        let legacy = HeaderValue::from_static("Sunday, 06-Nov-94 08:49:37 GMT");
+        assert_eq!(HttpDate::try_from(&legacy), Ok(d));
+
+        let opaque = HeaderValue::from_bytes(b"Sun, 06 Nov 1994 08:49:37 \xff").unwrap();
+        assert!(HttpDate::try_from(&opaque).is_err());
+    }
+
+    #[test]
+    fn test_get_date() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(get_date(&headers, DATE), None);
+        headers.insert(DATE, d.into());
+        assert_eq!(get_date(&headers, DATE), Some(Ok(d)));
+        assert_eq!(get_date(&headers, "date"), Some(Ok(d)));
+        headers.append(DATE, d.into());
+        assert!(get_date(&headers, DATE).unwrap().is_err());
+        headers.insert(EXPIRES, HeaderValue::from_bytes(b"\xfe\xff").unwrap());
+        assert!(get_date(&headers, &EXPIRES).unwrap().is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt::{self, Display, Formatter};
+
+use crate::HttpDate;
+
+impl HttpDate {
+    /// Describe this date relative to `now` for logs and error pages.
+    ///
+    /// Produces English text like `expired 3 hours ago` for dates in the
+    /// past or `fresh for 2 days` for dates in the future. The amount is
+    /// rounded down to the largest whole unit. A date equal to `now` is
+    /// `expired just now`, as a response is stale once its age reaches
+    /// the freshness lifetime (RFC 9111 section 4.2). Nothing is allocated.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let expires: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// let now: HttpDate = "Sun, 06 Nov 1994 12:00:00 GMT".parse().unwrap();
+    /// assert_eq!(expires.humanize_relative_to(now).to_string(), "expired 3 hours ago");
+    /// ```
+    pub fn humanize_relative_to(&self, now: HttpDate) -> impl Display {
+        Relative {
+            secs: self.secs_since_epoch() as i64 - now.secs_since_epoch() as i64,
+        }
+    }
+}
+
+struct Relative {
+    /// Seconds from now until the date, negative if it is in the past.
+    secs: i64,
+}
+
+impl Display for Relative {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.secs == 0 {
+            return f.write_str("expired just now");
+        }
+        let secs = self.secs.unsigned_abs();
+        let (n, unit) = if secs >= 86400 {
+            (secs / 86400, "day")
+        } else if secs >= 3600 {
+            (secs / 3600, "hour")
+        } else if secs >= 60 {
+            (secs / 60, "minute")
+        } else {
+            (secs, "second")
+        };
+        let s = if n == 1 { "" } else { "s" };
+        if self.secs < 0 {
+            write!(f, "expired {} {}{} ago", n, unit, s)
+        } else {
+            write!(f, "fresh for {} {}{}", n, unit, s)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::HttpDate;
+
+    fn relative(secs: i64) -> String {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let date = if secs < 0 {
+            now - Duration::from_secs(-secs as u64)
+        } else {
+            now + Duration::from_secs(secs as u64)
+        };
+        HttpDate::from(date)
+            .humanize_relative_to(HttpDate::from(now))
+            .to_string()
+    }
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(relative(0), "expired just now");
+        assert_eq!(relative(1), "fresh for 1 second");
+        assert_eq!(relative(-1), "expired 1 second ago");
+        assert_eq!(relative(-59), "expired 59 seconds ago");
+        assert_eq!(relative(-60), "expired 1 minute ago");
+        assert_eq!(relative(3 * 3600 + 3599), "fresh for 3 hours");
+        assert_eq!(relative(-2 * 86400), "expired 2 days ago");
+        assert_eq!(relative(400 * 86400), "fresh for 400 days");
+    }
+}
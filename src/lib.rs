@@ -7,66 +7,365 @@
 //! writing 1431696861 Unix time. Rust’s `SystemTime` is used to store
 //! these timestamps.
 //!
-//! This crate provides two public functions:
+//! The core of the crate is the `HttpDate` type. It parses and formats
+//! timestamps and converts from and to a system time. The `HttpDate` (8 bytes)
+//! is smaller than `SystemTime` (16 bytes) and using the display impl avoids a
+//! temporary allocation. `parse_http_date` and `fmt_http_date` are shortcuts
+//! that parse to and format from a system time directly.
 //!
-//! * `parse_http_date` to parse a HTTP datetime string to a system time
-//! * `fmt_http_date` to format a system time to a IMF-fixdate
+//! Around it the crate offers:
 //!
-//! In addition it exposes the `HttpDate` type that can be used to parse
-//! and format timestamps. Convert a sytem time to `HttpDate` and vice versa.
-//! The `HttpDate` (8 bytes) is smaller than `SystemTime` (16 bytes) and
-//! using the display impl avoids a temporary allocation.
-#![forbid(unsafe_code)]
-
-use std::error;
-use std::fmt::{self, Display, Formatter};
-use std::io;
+//! * more ways to parse: `ParseOptions` for lenient input, `find_dates` to
+//!   scan free text, `parse_http_dates` for lists, and `normalize_http_date`
+//!   to rewrite accepted dates as IMF-fixdates,
+//! * typed values for header fields, such as `RetryAfter`, `Age` and the
+//!   `typed`, `expires`, `conditional` and `freshness` modules,
+//! * calendar arithmetic in `calendar` and the cheap clock in `clock`,
+//! * interoperability with other crates behind the optional features below.
+//!
+//! Parsing never panics, whatever the input, and neither does formatting
+//! an `HttpDate`. Only the conversions from `SystemTime` panic for times
+//! that can't be represented, use `HttpDate::try_from_system_time` to
+//! handle those or `HttpDate::from_system_time_saturating` to clamp them.
+//!
+//! Parsing, comparing and formatting with `HttpDate::format_into` never
+//! allocate, and neither do errors. An `Error` is one byte that records
+//! why a date was rejected, it used to be zero-sized. This does not change
+//! the size of `Result<HttpDate, Error>`.
+//!
+//! # Optional features
+//!
+//! * `arbitrary`: generate valid `HttpDate` values for fuzzing.
+//! * `chrono`: convert between `HttpDate` and chrono's `DateTime<Utc>`
+//!   and `NaiveDateTime`.
+//! * `cli`: build the `httpdate` command line tool that converts between
+//!   epoch seconds, RFC 3339 timestamps and HTTP dates.
+//! * `cookie`: convert between `HttpDate` and `cookie::Expiration`,
+//!   see [`cookie`].
+//! * `defmt`: log `HttpDate` with `defmt` on embedded targets.
+//! * `detailed-errors`: record the offset of the problem and a copy of
+//!   the input in the `Error` type.
+//! * `ffi`: export C functions to parse and format dates, see [`ffi`].
+//! * `filetime`: convert between `HttpDate` and Windows `FILETIME` values
+//!   with `HttpDate::from_filetime` and `HttpDate::to_filetime`.
+//! * `headers`: implement `headers::Header` for the wrappers in [`typed`].
+//! * `http`: convert between `HttpDate` and `http::HeaderValue`, read
+//!   dates from a `HeaderMap` with [`get_date`] and get cached header
+//!   values from [`DateHeaderCache`].
+//! * `httparse`: extract the date header fields from `httparse` headers
+//!   with [`DateHeaders`].
+//! * `libc`: convert between `HttpDate` and `libc::timespec` and
+//!   `libc::timeval`.
+//! * `rayon`: parse and format many dates in parallel with
+//!   [`par_parse_http_dates`] and [`par_fmt_http_dates`].
+//! * `serde`: serialize `HttpDate` as an IMF-fixdate string, or as seconds
+//!   since the epoch for binary formats.
+//! * `std` (default): use `SystemTime`, read the clock and enable the
+//!   helpers that need the standard library. Without it the crate is
+//!   `no_std` and uses [`UnixTimestamp`] instead of `SystemTime`.
+//! * `test-util`: iterate over known good dates with [`vectors`] to test
+//!   other implementations and wrappers.
+//! * `wasm`: make `HttpDate::now()` work on `wasm32-unknown-unknown` and
+//!   convert from and to `web_time::SystemTime`.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "ffi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "ffi", deny(unsafe_code))]
+
+#[cfg(all(
+    not(feature = "std"),
+    any(
+        feature = "cookie",
+        feature = "ffi",
+        feature = "headers",
+        feature = "http",
+        feature = "httparse",
+        feature = "rayon",
+        feature = "serde",
+        feature = "wasm"
+    )
+))]
+compile_error!("this feature requires the `std` feature");
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
 use std::time::SystemTime;
 
-pub use date::HttpDate;
+#[cfg(feature = "http")]
+pub use crate::http::get_date;
+#[cfg(feature = "rayon")]
+pub use crate::rayon::{par_fmt_http_dates, par_parse_http_dates};
+pub use age::Age;
+#[cfg(feature = "std")]
+pub use clock::CoarseClock;
+pub use date::{HttpDate, IMF_FIXDATE_LEN};
+#[cfg(feature = "std")]
+pub use date_cache::DateHeaderCache;
+#[cfg(feature = "std")]
+pub use diagnose::Problem;
+pub use error::Error;
+#[cfg(feature = "httparse")]
+pub use header_block::DateHeaders;
+pub use options::{LenientHttpDate, ParseOptions};
+#[cfg(feature = "std")]
+pub use rate_limit::RateLimitReset;
+#[cfg(feature = "std")]
+pub use retry_after::{RetryAfter, RetryAfterBuilder};
+pub use scan::find_dates;
+#[cfg(feature = "std")]
+pub use skew::SkewEstimator;
+#[cfg(feature = "test-util")]
+pub use test_vectors::vectors;
+pub use timestamp::UnixTimestamp;
 
+mod age;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod calendar;
+#[cfg(feature = "chrono")]
+mod chrono;
+mod clf;
+pub mod clock;
+pub mod conditional;
+#[cfg(feature = "cookie")]
+pub mod cookie;
 mod date;
+#[cfg(feature = "std")]
+mod date_cache;
+#[cfg(feature = "defmt")]
+mod defmt;
+pub mod delta_seconds;
+#[cfg(feature = "std")]
+mod diagnose;
+mod dos;
+mod error;
+pub mod expires;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "filetime")]
+mod filetime;
+#[cfg(feature = "std")]
+pub mod freshness;
+#[cfg(feature = "httparse")]
+mod header_block;
+#[cfg(feature = "http")]
+mod http;
+mod humanize;
+#[cfg(feature = "libc")]
+mod libc;
+mod list;
+mod ntp;
+mod options;
+#[cfg(kani)]
+mod proofs;
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "std")]
+mod retry_after;
+mod rfc3339;
+mod scan;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+mod skew;
+#[cfg(feature = "test-util")]
+pub mod test_vectors;
+mod timestamp;
+pub mod typed;
+pub mod warning;
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+mod wasm;
 
-/// An opaque error type for all parsing errors.
-#[derive(Debug)]
-pub struct Error(());
+/// Parse a date from an HTTP header field.
+///
+/// Supports the preferred IMF-fixdate and the legacy RFC 805 and
+/// ascdate formats. Two digit years are mapped to dates between
+/// 1970 and 2069.
+#[cfg(feature = "std")]
+pub fn parse_http_date(s: &str) -> Result<SystemTime, Error> {
+    s.parse::<HttpDate>().map(|d| d.into())
+}
 
-impl error::Error for Error {}
+/// Parse a date from the raw bytes of an HTTP header field.
+///
+/// Like [`parse_http_date`] but takes the bytes as received, there is no
+/// need to check that they are UTF-8 first.
+///
+/// ```
+/// let date = httpdate::parse_http_date_bytes(b"Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+/// assert_eq!(httpdate::fmt_http_date(date), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert!(httpdate::parse_http_date_bytes(b"Sun, 06 Nov 1994 08:49:37 \xff").is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_http_date_bytes(s: &[u8]) -> Result<SystemTime, Error> {
+    HttpDate::parse_bytes(s).map(|d| d.into())
+}
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        f.write_str("string contains no or an invalid date")
-    }
+/// Parse many dates from HTTP header fields.
+///
+/// ```
+/// let dates = ["Sun Nov  6 08:49:37 1994", "Sun Nov  6 08:49:38 1994", "soon"];
+/// let parsed: Vec<_> = httpdate::parse_http_dates(dates.iter().copied()).collect();
+/// assert!(parsed[0].is_ok() && parsed[1].is_ok() && parsed[2].is_err());
+/// ```
+pub fn parse_http_dates<'a, I>(dates: I) -> impl Iterator<Item = Result<HttpDate, Error>> + 'a
+where
+    I: IntoIterator<Item = &'a str>,
+    I::IntoIter: 'a,
+{
+    dates
+        .into_iter()
+        .map(|s| HttpDate::parse_bytes(s.as_bytes()))
+}
+
+/// Check if a header field value is a valid HTTP date.
+///
+/// Accepts the same inputs as [`HttpDate`]'s `FromStr` impl but is faster
+/// for invalid input as it does not build an error.
+///
+/// ```
+/// assert!(httpdate::is_valid_http_date("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// assert!(!httpdate::is_valid_http_date("Mon, 06 Nov 1994 08:49:37 GMT"));
+/// ```
+pub fn is_valid_http_date(s: &str) -> bool {
+    HttpDate::is_valid(s.as_bytes())
+}
+
+/// Rewrite a date in any accepted format as IMF-fixdate.
+///
+/// Returns the input unchanged as `Cow::Borrowed` if it is already an
+/// IMF-fixdate without surrounding whitespace, and a new string otherwise.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// let date = httpdate::normalize_http_date("Sunday, 06-Nov-94 08:49:37 GMT").unwrap();
+/// assert_eq!(date, "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert!(matches!(date, Cow::Owned(_)));
+/// let date = httpdate::normalize_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+/// assert!(matches!(date, Cow::Borrowed(_)));
+/// ```
+#[cfg(feature = "std")]
+pub fn normalize_http_date(s: &str) -> Result<Cow<'_, str>, Error> {
+    normalize_http_date_with(s, &ParseOptions::new())
 }
 
-impl From<Error> for io::Error {
-    fn from(e: Error) -> io::Error {
-        io::Error::new(io::ErrorKind::Other, e)
+/// Rewrite a date accepted by `options` as IMF-fixdate.
+///
+/// Like [`normalize_http_date`] but also repairs the deviations that
+/// `options` accepts, for example in a gateway that forwards headers from
+/// legacy servers.
+///
+/// ```
+/// # use httpdate::ParseOptions;
+/// let date = "Sun 6 Nov 1994 9:49:37 GMT+0100";
+/// let normalized = httpdate::normalize_http_date_with(date, &ParseOptions::BROWSER).unwrap();
+/// assert_eq!(normalized, "Sun, 06 Nov 1994 08:49:37 GMT");
+/// ```
+#[cfg(feature = "std")]
+pub fn normalize_http_date_with<'a>(
+    s: &'a str,
+    options: &ParseOptions,
+) -> Result<Cow<'a, str>, Error> {
+    let date = options.parse(s)?;
+    let mut buf = [0; IMF_FIXDATE_LEN];
+    if date.format_into(&mut buf) == s.as_bytes() {
+        Ok(Cow::Borrowed(s))
+    } else {
+        Ok(Cow::Owned(date.to_string()))
     }
 }
 
-/// Parse a date from an HTTP header field.
+/// The `Last-Modified` date of a response built from several sources: the
+/// latest of their modification times, truncated to whole seconds.
 ///
-/// Supports the preferred IMF-fixdate and the legacy RFC 805 and
-/// ascdate formats. Two digit years are mapped to dates between
-/// 1970 and 2069.
-pub fn parse_http_date(s: &str) -> Result<SystemTime, Error> {
-    s.parse::<HttpDate>().map(|d| d.into())
+/// Returns `None` if there are no times. Times before 1970 count as the
+/// epoch and times after 9999 as [`HttpDate::MAX`].
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// let times = [784111777, 784111779, 784111778].map(|s| UNIX_EPOCH + Duration::from_secs(s));
+/// let date = httpdate::last_modified_of(times).unwrap();
+/// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:39 GMT");
+/// assert_eq!(httpdate::last_modified_of([]), None);
+/// ```
+#[cfg(feature = "std")]
+pub fn last_modified_of<I>(times: I) -> Option<HttpDate>
+where
+    I: IntoIterator<Item = SystemTime>,
+{
+    times
+        .into_iter()
+        .max()
+        .map(HttpDate::from_system_time_saturating)
+}
+
+/// The value of [`HttpDate::EXPIRED`] for an `Expires` header field.
+///
+/// Sending it forces HTTP/1.0 caches that ignore `Cache-Control` to
+/// revalidate the response.
+///
+/// ```
+/// assert_eq!(httpdate::fmt_expired(), "Thu, 01 Jan 1970 00:00:00 GMT");
+/// ```
+pub fn fmt_expired() -> &'static str {
+    "Thu, 01 Jan 1970 00:00:00 GMT"
 }
 
 /// Format a date to be used in a HTTP header field.
 ///
 /// Dates are formatted as IMF-fixdate: `Fri, 15 May 2015 15:34:21 GMT`.
+///
+/// # Panics
+///
+/// Panics if the time is before 1970 or after 9999, see
+/// [`HttpDate::try_from_system_time`].
+#[cfg(feature = "std")]
 pub fn fmt_http_date(d: SystemTime) -> String {
     format!("{}", HttpDate::from(d))
 }
 
+/// Format many dates into one string, separated by `separator`.
+///
+/// The output is appended to `out` without allocating per date.
+///
+/// ```
+/// # use httpdate::HttpDate;
+/// let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// let mut out = String::new();
+/// httpdate::fmt_http_dates(vec![d, d], "\t", &mut out);
+/// assert_eq!(out, "Sun, 06 Nov 1994 08:49:37 GMT\tSun, 06 Nov 1994 08:49:37 GMT");
+/// ```
+#[cfg(feature = "std")]
+pub fn fmt_http_dates<I>(dates: I, separator: &str, out: &mut String)
+where
+    I: IntoIterator<Item = HttpDate>,
+{
+    use std::fmt::Write;
+
+    let dates = dates.into_iter();
+    out.reserve(dates.size_hint().0 * (IMF_FIXDATE_LEN + separator.len()));
+    for (i, date) in dates.enumerate() {
+        if i > 0 {
+            out.push_str(separator);
+        }
+        write!(out, "{}", date).expect("writing to a String does not fail");
+    }
+}
+
 #[cfg(test)]
+#[cfg(feature = "std")]
 mod tests {
+    use std::borrow::Cow;
     use std::str;
-    use std::time::{Duration, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-    use super::{fmt_http_date, parse_http_date, HttpDate};
+    use super::{
+        fmt_expired, fmt_http_date, fmt_http_dates, is_valid_http_date, last_modified_of,
+        normalize_http_date, normalize_http_date_with, parse_http_date, parse_http_dates, Error,
+        HttpDate, ParseOptions, IMF_FIXDATE_LEN,
+    };
 
     #[test]
     fn test_rfc_example() {
@@ -124,6 +423,19 @@ mod tests {
         assert_eq!(fmt_http_date(d), "Sun, 02 Oct 2016 14:44:11 GMT");
     }
 
+    #[test]
+    fn test_format_into() {
+        let mut buf = [0; IMF_FIXDATE_LEN];
+        for secs in (0..253402300800).step_by(86399 * 367 + 7) {
+            let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs));
+            assert_eq!(d.format_into(&mut buf), d.to_string().as_bytes());
+        }
+        assert_eq!(
+            HttpDate::MAX.format_into(&mut buf),
+            b"Fri, 31 Dec 9999 23:59:59 GMT"
+        );
+    }
+
     #[allow(dead_code)]
     fn testcase(data: &[u8]) {
         if let Ok(s) = str::from_utf8(data) {
@@ -138,6 +450,136 @@ mod tests {
     #[test]
     fn size_of() {
         assert_eq!(::std::mem::size_of::<HttpDate>(), 8);
+        // One byte for the reason of the error, the result is as large as
+        // with the zero-sized error of earlier versions.
+        #[cfg(not(feature = "detailed-errors"))]
+        assert_eq!(::std::mem::size_of::<Error>(), 1);
+        #[cfg(not(feature = "detailed-errors"))]
+        assert_eq!(::std::mem::size_of::<Result<HttpDate, Error>>(), 16);
+    }
+
+    #[test]
+    fn test_parse_raw() {
+        let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        let s = b"Sun, 06 Nov 1994 08:49:37 GMT";
+        let mut buf = [0; 64];
+        buf[..s.len()].copy_from_slice(s);
+        assert_eq!(HttpDate::parse_raw(&buf), Ok(d));
+        buf[s.len()..s.len() + 2].copy_from_slice(b"\r\n");
+        assert_eq!(HttpDate::parse_raw(&buf), Ok(d));
+        assert_eq!(
+            HttpDate::parse_raw(b"Sun, 06 Nov 1994 08:49:37 GMT\r\n"),
+            Ok(d)
+        );
+        assert!(HttpDate::parse_raw(b"Sun, 06 Nov 1994 08:49:37 GMT\0x").is_err());
+        assert!(HttpDate::parse_raw(b"\0Sun, 06 Nov 1994 08:49:37 GMT").is_err());
+        assert!(HttpDate::parse_raw(b"\0\0").is_err());
+    }
+
+    #[test]
+    fn test_saturating() {
+        assert_eq!(HttpDate::MAX.to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+        assert_eq!(HttpDate::MIN.to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        let max = UNIX_EPOCH + Duration::from_secs(253402300800);
+        assert_eq!(HttpDate::from_system_time_saturating(max), HttpDate::MAX);
+        assert_eq!(
+            HttpDate::from_system_time_saturating(max - Duration::from_millis(1)),
+            HttpDate::MAX
+        );
+        assert_eq!(
+            HttpDate::from_system_time_saturating(UNIX_EPOCH - Duration::from_nanos(1)),
+            HttpDate::MIN
+        );
+        let now = SystemTime::now();
+        assert_eq!(
+            HttpDate::from_system_time_saturating(now),
+            HttpDate::from(now)
+        );
+    }
+
+    #[test]
+    fn test_rfc850() {
+        let fmt = |secs| {
+            HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs))
+                .rfc850()
+                .map(|d| d.to_string())
+        };
+        assert_eq!(fmt(0).unwrap(), "Thursday, 01-Jan-70 00:00:00 GMT");
+        assert_eq!(fmt(951868799).unwrap(), "Tuesday, 29-Feb-00 23:59:59 GMT");
+        assert_eq!(fmt(3155759999).unwrap(), "Tuesday, 31-Dec-69 23:59:59 GMT");
+        assert!(fmt(3155760000).is_none());
+        for secs in (0..3155760000).step_by(86400 * 365 + 12345) {
+            let s = fmt(secs).unwrap();
+            assert_eq!(
+                s.parse::<HttpDate>(),
+                Ok(HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_expired() {
+        assert_eq!(HttpDate::EXPIRED.to_string(), fmt_expired());
+        assert_eq!(fmt_expired().parse(), Ok(HttpDate::EXPIRED));
+    }
+
+    #[test]
+    fn test_now() {
+        let before = SystemTime::now() - Duration::from_secs(1);
+        let now = HttpDate::now();
+        assert!(before <= SystemTime::from(now));
+        assert!(SystemTime::from(now) <= SystemTime::now());
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let metadata = std::fs::metadata(file!()).unwrap();
+        let date = HttpDate::from_metadata(&metadata).unwrap();
+        assert!(date <= HttpDate::now());
+        assert_eq!(
+            SystemTime::from(date),
+            UNIX_EPOCH
+                + Duration::from_secs(
+                    metadata
+                        .modified()
+                        .unwrap()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs()
+                )
+        );
+    }
+
+    #[test]
+    fn test_last_modified_of() {
+        let base = UNIX_EPOCH + Duration::from_secs(784111777);
+        let times = vec![
+            base + Duration::from_millis(999),
+            base - Duration::from_secs(1),
+            base + Duration::from_millis(500),
+        ];
+        let date = last_modified_of(times).unwrap();
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(last_modified_of(Vec::new()), None);
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+        assert_eq!(last_modified_of(vec![before_epoch]), Some(HttpDate::MIN));
+        let far = UNIX_EPOCH + Duration::from_secs(1 << 40);
+        assert_eq!(last_modified_of(vec![base, far]), Some(HttpDate::MAX));
+    }
+
+    #[test]
+    fn test_seconds_until() {
+        let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(100));
+        let at = |ms| UNIX_EPOCH + Duration::from_millis(ms);
+        assert_eq!(d.seconds_until(at(0)), Some(100));
+        assert_eq!(d.seconds_until(at(98_500)), Some(2));
+        assert_eq!(d.seconds_until(at(99_999)), Some(1));
+        assert_eq!(d.seconds_until(at(100_000)), Some(0));
+        assert_eq!(d.seconds_until(at(100_001)), None);
+        assert_eq!(
+            d.seconds_until(UNIX_EPOCH - Duration::from_secs(1)),
+            Some(101)
+        );
     }
 
     #[test]
@@ -148,7 +590,15 @@ mod tests {
         let a_date: HttpDate = a.into();
         let b_date: HttpDate = b.into();
         assert!(a_date < b_date);
-        assert_eq!(a_date.cmp(&b_date), ::std::cmp::Ordering::Less)
+        assert_eq!(a_date.cmp(&b_date), ::std::cmp::Ordering::Less);
+
+        let mut dates: Vec<HttpDate> = [86400 * 400, 0, 59, 60, 3600, 86400 * 31, 86400 * 365]
+            .iter()
+            .map(|&secs| HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs)))
+            .collect();
+        dates.sort();
+        let times: Vec<SystemTime> = dates.iter().map(|&d| d.into()).collect();
+        assert!(times.windows(2).all(|w| w[0] < w[1]));
     }
 
     #[test]
@@ -157,4 +607,224 @@ mod tests {
         let parsed = "Sun, 07 Nov 1994 08:48:37 GMT".parse::<HttpDate>();
         assert!(parsed.is_err())
     }
+
+    #[test]
+    fn test_parse_http_dates() {
+        let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        let dates = [
+            "Sun Nov  6 08:49:37 1994",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 08:49:37",
+            "Sun Nov  6 08:49:37 1994",
+        ];
+        let parsed: Vec<_> = parse_http_dates(dates.iter().copied()).collect();
+        let expected: Vec<_> = dates.iter().map(|s| s.parse::<HttpDate>()).collect();
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed[0], Ok(d));
+        assert!(parsed[4].is_err());
+    }
+
+    #[test]
+    fn test_normalize_http_date() {
+        for s in &[
+            "Sun, 06 Nov 1994 08:49:37 GMT ",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            let n = normalize_http_date(s).unwrap();
+            assert!(matches!(n, Cow::Owned(_)));
+            assert_eq!(n, "Sun, 06 Nov 1994 08:49:37 GMT");
+        }
+        let n = normalize_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert!(matches!(n, Cow::Borrowed(_)));
+        assert!(normalize_http_date("Mon, 06 Nov 1994 08:49:37 GMT").is_err());
+        assert!(normalize_http_date("Sun, 6 Nov 1994 08:49:37 GMT").is_err());
+    }
+
+    #[test]
+    fn test_normalize_http_date_with() {
+        for s in &[
+            "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Sun 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 08:49:37 GMT+0000",
+            "Sun, 06 Nov 1994 8:49:37 GMT",
+            "Sun, 06 11 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            let n = normalize_http_date_with(s, &ParseOptions::BROWSER).unwrap();
+            assert!(matches!(n, Cow::Owned(_)), "{}", s);
+            assert_eq!(n, "Sun, 06 Nov 1994 08:49:37 GMT");
+        }
+        let s = "Sun, 06 Nov 1994 08:49:37 GMT";
+        let n = normalize_http_date_with(s, &ParseOptions::BROWSER).unwrap();
+        assert!(matches!(n, Cow::Borrowed(_)));
+        let n = normalize_http_date_with("Mon, 30 Feb 2015 08:49:37 GMT", &ParseOptions::BROWSER);
+        assert_eq!(n.unwrap(), "Sat, 28 Feb 2015 08:49:37 GMT");
+        let options = ParseOptions::new().zone_offset();
+        assert!(normalize_http_date_with("Sun, 6 Nov 1994 08:49:37 GMT", &options).is_err());
+    }
+
+    #[test]
+    fn test_fmt_http_dates() {
+        let a = HttpDate::from(UNIX_EPOCH);
+        let b = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        let mut out = String::from("dates: ");
+        fmt_http_dates(vec![a, b], ", ", &mut out);
+        assert_eq!(
+            out,
+            "dates: Thu, 01 Jan 1970 00:00:00 GMT, Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+        fmt_http_dates(vec![], ", ", &mut out);
+        assert!(out.ends_with("GMT"));
+    }
+
+    #[test]
+    fn test_all_month_and_weekday_names() {
+        let names = [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ];
+        for days in 0..400 {
+            let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(days * 86400 + 3661));
+            let imf = d.to_string();
+            assert_eq!(imf.parse::<HttpDate>(), Ok(d));
+            let wday = names.iter().find(|n| n.starts_with(&imf[..3])).unwrap();
+            let rfc850 = format!(
+                "{}, {}-{}-{} {} GMT",
+                wday,
+                &imf[5..7],
+                &imf[8..11],
+                &imf[14..16],
+                &imf[17..25]
+            );
+            assert_eq!(rfc850.parse::<HttpDate>(), Ok(d));
+            let asctime = format!(
+                "{} {} {:>2} {} {}",
+                &imf[..3],
+                &imf[8..11],
+                imf[5..7].trim_start_matches('0'),
+                &imf[17..25],
+                &imf[12..16]
+            );
+            assert_eq!(asctime.parse::<HttpDate>(), Ok(d));
+        }
+    }
+
+    #[test]
+    fn test_imf_fixdate_single_byte_changes() {
+        // Every accepted IMF-fixdate must format to exactly the input.
+        let date = *b"Sun, 06 Nov 1994 08:49:37 GMT";
+        for i in 0..date.len() {
+            for &b in b"0159:/ ,\x00\x7fAZaz" {
+                let mut s = date;
+                s[i] = b;
+                let s = str::from_utf8(&s).unwrap();
+                if let Ok(d) = s.parse::<HttpDate>() {
+                    assert_eq!(d.to_string(), s);
+                }
+            }
+        }
+        assert!("Sun, 06 Nov 1994 08:49:3: GMT".parse::<HttpDate>().is_err());
+        assert!("Sun, 06 Nov 1994 08:4/:37 GMT".parse::<HttpDate>().is_err());
+        assert!("Sun, 06 Nov 1994 08:49:37 GMX".parse::<HttpDate>().is_err());
+    }
+
+    #[test]
+    fn test_parse_never_panics() {
+        // Random bytes and random changes to valid dates of every format.
+        let samples: &[&[u8]] = &[
+            b"Sun, 06 Nov 1994 08:49:37 GMT",
+            b"Sunday, 06-Nov-94 08:49:37 GMT",
+            b"Sun Nov  6 08:49:37 1994",
+        ];
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for i in 0..100_000 {
+            let mut input = samples[i % samples.len()].to_vec();
+            if i % 4 == 0 {
+                input.truncate(next() as usize % (input.len() + 1));
+            }
+            for _ in 0..next() % 4 {
+                let at = next() as usize % (input.len() + 1);
+                let byte = if i % 2 == 0 {
+                    b"0123456789 ,:-GMTSunNov"[next() as usize % 23]
+                } else {
+                    next() as u8
+                };
+                if at < input.len() && next() % 2 == 0 {
+                    input[at] = byte;
+                } else {
+                    input.insert(at, byte);
+                }
+            }
+            let parsed = HttpDate::parse_bytes(&input);
+            if let Ok(d) = parsed {
+                let _ = d.to_string();
+            }
+            if let Ok(s) = str::from_utf8(&input) {
+                assert_eq!(is_valid_http_date(s), parsed.is_ok(), "{:?}", s);
+            }
+        }
+        let max = UNIX_EPOCH + Duration::from_secs(253402300800);
+        assert!(HttpDate::try_from_system_time(max).is_err());
+        assert!(HttpDate::try_from_system_time(max - Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_error_eq_clone() {
+        let a: Error = parse_http_date("not a date").unwrap_err();
+        let b = a.clone();
+        assert_eq!(a, b);
+        assert_eq!("not a date".parse::<HttpDate>(), Err(b));
+    }
+
+    #[test]
+    fn test_error_predicates() {
+        let e = parse_http_date("Sun, 06 Nov 1994 08:49:37 UTC").unwrap_err();
+        assert!(e.is_unrecognized_format());
+        assert!(!e.is_out_of_range());
+        assert!(!e.is_invalid_date());
+        assert!(!e.is_unavailable());
+        let e = parse_http_date("Sun, 06 Nov 1994 25:49:37 GMT").unwrap_err();
+        assert!(e.is_out_of_range());
+        let e = parse_http_date("Sun Nov 10 08:00:00 1000").unwrap_err();
+        assert!(e.is_out_of_range());
+        let e = parse_http_date("Sun, 07 Nov 1994 08:48:37 GMT").unwrap_err();
+        assert!(e.is_invalid_date());
+        let e = parse_http_date("Tue, 30 Feb 2016 08:48:37 GMT").unwrap_err();
+        assert!(e.is_invalid_date());
+    }
+
+    #[cfg(feature = "detailed-errors")]
+    #[test]
+    fn test_detailed_error() {
+        let e = parse_http_date(" Sun, 06 Nov 1994 08:49:37 GMX").unwrap_err();
+        assert_eq!(e.offset(), Some(26));
+        assert_eq!(e.input(), b" Sun, 06 Nov 1994 08:49:37 GMX");
+        assert_eq!(
+            e.to_string(),
+            "string contains no or an invalid date: unrecognized format \
+             at byte 26 in \" Sun, 06 Nov 1994 08:49:37 GMX\""
+        );
+        let e = parse_http_date("Sun, 07 Nov 1994 08:48:37 GMT").unwrap_err();
+        assert_eq!(e.offset(), None);
+        let e = parse_http_date("").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "string contains no or an invalid date: unrecognized format at byte 0"
+        );
+    }
 }
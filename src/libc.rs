@@ -0,0 +1,105 @@
+//! Conversions from and to `libc::timespec` and `libc::timeval`, enabled
+//! with the `libc` feature.
+//!
+//! Converting to `HttpDate` truncates sub-second precision and fails for
+//! times before 1970 or after 9999. Converting from `HttpDate` fails if
+//! the date does not fit into `time_t`, which is 32 bits on some targets.
+
+use core::convert::TryFrom;
+
+use ::libc::{time_t, timespec, timeval};
+
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+fn from_time_t(secs: time_t) -> Result<HttpDate, Error> {
+    u64::try_from(secs)
+        .ok()
+        .and_then(HttpDate::from_secs_since_epoch)
+        .ok_or_else(|| Error::new(ErrorKind::Range, None))
+}
+
+fn to_time_t(v: HttpDate) -> Result<time_t, Error> {
+    time_t::try_from(v.secs_since_epoch()).map_err(|_| Error::new(ErrorKind::Range, None))
+}
+
+impl TryFrom<timespec> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: timespec) -> Result<HttpDate, Error> {
+        from_time_t(v.tv_sec)
+    }
+}
+
+impl TryFrom<HttpDate> for timespec {
+    type Error = Error;
+
+    fn try_from(v: HttpDate) -> Result<timespec, Error> {
+        Ok(timespec {
+            tv_sec: to_time_t(v)?,
+            tv_nsec: 0,
+        })
+    }
+}
+
+impl TryFrom<timeval> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: timeval) -> Result<HttpDate, Error> {
+        from_time_t(v.tv_sec)
+    }
+}
+
+impl TryFrom<HttpDate> for timeval {
+    type Error = Error;
+
+    fn try_from(v: HttpDate) -> Result<timeval, Error> {
+        Ok(timeval {
+            tv_sec: to_time_t(v)?,
+            tv_usec: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use ::libc::{timespec, timeval};
+
+    use crate::HttpDate;
+
+    #[test]
+    fn test_timespec() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let ts = timespec {
+            tv_sec: 784111777,
+            tv_nsec: 999_999_999,
+        };
+        assert_eq!(HttpDate::try_from(ts).unwrap(), d);
+        let ts = timespec::try_from(d).unwrap();
+        assert_eq!((ts.tv_sec, ts.tv_nsec), (784111777, 0));
+        let before = timespec {
+            tv_sec: -1,
+            tv_nsec: 500_000_000,
+        };
+        assert!(HttpDate::try_from(before).unwrap_err().is_out_of_range());
+    }
+
+    #[test]
+    fn test_timeval() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let tv = timeval {
+            tv_sec: 784111777,
+            tv_usec: 999_999,
+        };
+        assert_eq!(HttpDate::try_from(tv).unwrap(), d);
+        let tv = timeval::try_from(d).unwrap();
+        assert_eq!((tv.tv_sec, tv.tv_usec), (784111777, 0));
+        let before = timeval {
+            tv_sec: -1,
+            tv_usec: 0,
+        };
+        assert!(HttpDate::try_from(before).is_err());
+    }
+}
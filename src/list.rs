@@ -0,0 +1,32 @@
+/// Split a comma separated header field into its trimmed, non-empty elements.
+///
+/// Commas in quoted strings are skipped.
+pub(crate) fn split_list(field: &str) -> impl Iterator<Item = &str> {
+    let mut rest = field;
+    core::iter::from_fn(move || loop {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = find_separator(rest.as_bytes());
+        let value = rest[..end].trim();
+        rest = rest.get(end + 1..).unwrap_or("");
+        if !value.is_empty() {
+            return Some(value);
+        }
+    })
+}
+
+fn find_separator(s: &[u8]) -> usize {
+    let mut quoted = false;
+    let mut i = 0;
+    while i < s.len() {
+        match s[i] {
+            b'\\' if quoted => i += 1,
+            b'"' => quoted = !quoted,
+            b',' if !quoted => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+    s.len()
+}
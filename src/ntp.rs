@@ -0,0 +1,65 @@
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+/// Seconds from 1900-01-01 to 1970-01-01.
+const EPOCH_DIFF_SECS: u64 = 2_208_988_800;
+
+/// Seconds in an NTP era, the 32 bit seconds counter wraps after this.
+const ERA_SECS: u64 = 1 << 32;
+
+impl HttpDate {
+    /// Convert a 64 bit NTP timestamp, truncating the fraction.
+    ///
+    /// The upper 32 bits count the seconds since 1900 and wrap around on
+    /// 2036-02-07. Following RFC 4330 a timestamp with the most significant
+    /// bit set is in era 0 (1968 to 2036) and one without in era 1
+    /// (2036 to 2104). Returns an out of range error for times before 1970.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::from_ntp_timestamp(0xB267_1721_8000_0000).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// let date = HttpDate::from_ntp_timestamp(0x0000_0001_0000_0000).unwrap();
+    /// assert_eq!(date.to_string(), "Thu, 07 Feb 2036 06:28:17 GMT");
+    /// ```
+    pub fn from_ntp_timestamp(ntp: u64) -> Result<HttpDate, Error> {
+        let secs = ntp >> 32;
+        let secs = if secs & 0x8000_0000 != 0 {
+            secs
+        } else {
+            secs + ERA_SECS
+        };
+        secs.checked_sub(EPOCH_DIFF_SECS)
+            .and_then(HttpDate::from_secs_since_epoch)
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use crate::HttpDate;
+
+    #[test]
+    fn test_ntp_timestamp() {
+        let epoch = 2_208_988_800u64 << 32;
+        assert_eq!(
+            HttpDate::from_ntp_timestamp(epoch).unwrap(),
+            HttpDate::EPOCH
+        );
+        assert_eq!(
+            HttpDate::from_ntp_timestamp(epoch | 0xFFFF_FFFF).unwrap(),
+            HttpDate::EPOCH
+        );
+        let last = HttpDate::from_ntp_timestamp(0xFFFF_FFFF_FFFF_FFFF).unwrap();
+        assert_eq!(last.to_string(), "Thu, 07 Feb 2036 06:28:15 GMT");
+        let first = HttpDate::from_ntp_timestamp(0).unwrap();
+        assert_eq!(first.to_string(), "Thu, 07 Feb 2036 06:28:16 GMT");
+        let end = HttpDate::from_ntp_timestamp(0x7FFF_FFFF_0000_0000).unwrap();
+        assert_eq!(end.to_string(), "Tue, 26 Feb 2104 09:42:23 GMT");
+        assert!(HttpDate::from_ntp_timestamp(0x8000_0000_0000_0000)
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(HttpDate::from_ntp_timestamp(epoch - 1).is_err());
+    }
+}
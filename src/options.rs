@@ -0,0 +1,416 @@
+use core::fmt::{self, Display, Formatter};
+use core::ops::Deref;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use crate::date::Leniency;
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+/// What to do with dates after the limit.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum Limit {
+    Reject(HttpDate),
+    Clamp(HttpDate),
+}
+
+/// Parser settings for dates from untrusted or non-compliant sources.
+///
+/// Dates far in the future, like an `Expires` in the year 9999, can be used
+/// to keep poisoned entries in a cache forever. Set a limit to reject or
+/// clamp them while parsing. Other settings accept common deviations from
+/// the standard formats.
+///
+/// ```
+/// # use std::time::{Duration, SystemTime};
+/// # use httpdate::ParseOptions;
+/// let year = Duration::from_secs(365 * 86400);
+/// let options = ParseOptions::new().reject_if_after(SystemTime::now() + year);
+/// assert!(options.parse("Fri, 31 Dec 9999 23:59:59 GMT").is_err());
+/// assert!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT").is_ok());
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ParseOptions {
+    limit: Option<Limit>,
+    leniency: Leniency,
+}
+
+impl ParseOptions {
+    /// Accept all the deviations from the standard formats that have
+    /// settings, like web browsers do. Sets no limit.
+    pub const BROWSER: ParseOptions = ParseOptions {
+        limit: None,
+        leniency: Leniency {
+            clamp_day: true,
+            zone_offset: true,
+            missing_comma: true,
+            short_time: true,
+            short_day: true,
+            numeric_month: true,
+        },
+    };
+
+    /// Accept every valid date, like the `FromStr` impl of `HttpDate`.
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// Return an out of range error for dates after `limit`.
+    ///
+    /// Limits before 1970 are treated as 1970.
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn reject_if_after(mut self, limit: SystemTime) -> ParseOptions {
+        self.limit = limit_date(limit).map(Limit::Reject);
+        self
+    }
+
+    /// Replace dates after `limit` with the limit, truncated to seconds.
+    ///
+    /// Limits before 1970 are treated as 1970.
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn clamp_if_after(mut self, limit: SystemTime) -> ParseOptions {
+        self.limit = limit_date(limit).map(Limit::Clamp);
+        self
+    }
+
+    /// Move days after the end of the month to the last day of the month.
+    ///
+    /// Some devices send impossible dates like 31 April or 30 February.
+    /// For cache validation an approximate date is better than none. The
+    /// weekday of these dates is not checked.
+    ///
+    /// ```
+    /// # use httpdate::ParseOptions;
+    /// let options = ParseOptions::new().clamp_day();
+    /// let date = options.parse("Mon, 30 Feb 2015 08:49:37 GMT").unwrap();
+    /// assert_eq!(date.to_string(), "Sat, 28 Feb 2015 08:49:37 GMT");
+    /// ```
+    pub fn clamp_day(mut self) -> ParseOptions {
+        self.leniency.clamp_day = true;
+        self
+    }
+
+    /// Accept an offset after the `GMT` zone, like `GMT+0000` or `GMT-0700`.
+    ///
+    /// Some stacks append the offset from UTC to the zone. The offset is
+    /// applied, `GMT+0100` is one hour ahead of UTC.
+    ///
+    /// ```
+    /// # use httpdate::ParseOptions;
+    /// let options = ParseOptions::new().zone_offset();
+    /// let date = options.parse("Sun, 06 Nov 1994 09:49:37 GMT+0100").unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    pub fn zone_offset(mut self) -> ParseOptions {
+        self.leniency.zone_offset = true;
+        self
+    }
+
+    /// Accept an IMF-fixdate without the comma after the weekday, like
+    /// `Sun 06 Nov 1994 08:49:37 GMT`.
+    pub fn missing_comma(mut self) -> ParseOptions {
+        self.leniency.missing_comma = true;
+        self
+    }
+
+    /// Accept times with single digit hours, minutes and seconds in
+    /// IMF-fixdates, like `8:49:7`.
+    ///
+    /// ```
+    /// # use httpdate::ParseOptions;
+    /// let options = ParseOptions::new().short_time();
+    /// let date = options.parse("Sun, 06 Nov 1994 8:49:7 GMT").unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:07 GMT");
+    /// ```
+    pub fn short_time(mut self) -> ParseOptions {
+        self.leniency.short_time = true;
+        self
+    }
+
+    /// Accept a day of the month with a single digit in IMF-fixdates, like
+    /// `Sun, 6 Nov 1994 08:49:37 GMT`.
+    ///
+    /// ```
+    /// # use httpdate::ParseOptions;
+    /// let options = ParseOptions::new().short_day();
+    /// let date = options.parse("Sun, 6 Nov 1994 08:49:37 GMT").unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    pub fn short_day(mut self) -> ParseOptions {
+        self.leniency.short_day = true;
+        self
+    }
+
+    /// Accept a two digit month number in place of the month name in
+    /// IMF-fixdates, like `Sun, 06 11 1994 08:49:37 GMT`.
+    pub fn numeric_month(mut self) -> ParseOptions {
+        self.leniency.numeric_month = true;
+        self
+    }
+
+    /// Parse a date from an HTTP header field.
+    pub fn parse(&self, s: &str) -> Result<HttpDate, Error> {
+        self.parse_bytes(s.as_bytes())
+    }
+
+    /// Parse a date from the raw bytes of an HTTP header field.
+    pub fn parse_bytes(&self, s: &[u8]) -> Result<HttpDate, Error> {
+        let date = HttpDate::parse_lenient(s, self.leniency)?;
+        match self.limit {
+            Some(Limit::Reject(limit)) if date > limit => {
+                Err(Error::new(ErrorKind::Range, None).with_input(s, 0))
+            }
+            Some(Limit::Clamp(limit)) => Ok(date.min(limit)),
+            _ => Ok(date),
+        }
+    }
+}
+
+/// A date parsed with the [`ParseOptions::BROWSER`] settings.
+///
+/// Use it where only `FromStr` can be configured, for example with serde
+/// or clap. It dereferences to the parsed `HttpDate`.
+///
+/// ```
+/// # use httpdate::LenientHttpDate;
+/// let date: LenientHttpDate = "Sun 06 Nov 1994 8:49:37 GMT+0000".parse().unwrap();
+/// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct LenientHttpDate(pub HttpDate);
+
+impl FromStr for LenientHttpDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<LenientHttpDate, Error> {
+        ParseOptions::BROWSER.parse(s).map(LenientHttpDate)
+    }
+}
+
+impl Deref for LenientHttpDate {
+    type Target = HttpDate;
+
+    fn deref(&self) -> &HttpDate {
+        &self.0
+    }
+}
+
+impl From<LenientHttpDate> for HttpDate {
+    fn from(v: LenientHttpDate) -> HttpDate {
+        v.0
+    }
+}
+
+impl Display for LenientHttpDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The limit as a date, `None` if it is after every date.
+#[cfg(feature = "std")]
+fn limit_date(limit: SystemTime) -> Option<HttpDate> {
+    match HttpDate::try_from_system_time(limit) {
+        Ok(date) => Some(date),
+        Err(_) if limit < SystemTime::UNIX_EPOCH => Some(HttpDate::EPOCH),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{LenientHttpDate, ParseOptions};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_limit() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        let max = "Fri, 31 Dec 9999 23:59:59 GMT";
+        let s = "Sun, 06 Nov 1994 08:49:37 GMT";
+
+        assert!(ParseOptions::new().parse(max).is_ok());
+
+        let reject = ParseOptions::new().reject_if_after(at(784111777));
+        assert_eq!(reject.parse(s), Ok(d));
+        assert!(reject.parse(max).unwrap_err().is_out_of_range());
+        let reject = ParseOptions::new().reject_if_after(at(784111776));
+        assert!(reject.parse(s).is_err());
+        let reject = ParseOptions::new().reject_if_after(at(1 << 40));
+        assert!(reject.parse(max).is_ok());
+
+        let clamp = ParseOptions::new().clamp_if_after(at(784111777) + Duration::from_millis(500));
+        assert_eq!(clamp.parse(s), Ok(d));
+        assert_eq!(clamp.parse(max), Ok(d));
+        let clamp = ParseOptions::new().clamp_if_after(UNIX_EPOCH - Duration::from_secs(1));
+        assert_eq!(clamp.parse(s), Ok(HttpDate::EPOCH));
+        assert!(clamp.parse("soon").is_err());
+    }
+
+    #[test]
+    fn test_clamp_day() {
+        let options = ParseOptions::new().clamp_day();
+        let parse = |s| options.parse(s).map(|d| d.to_string());
+        assert_eq!(
+            parse("Thu, 31 Apr 2015 08:49:37 GMT").unwrap(),
+            "Thu, 30 Apr 2015 08:49:37 GMT"
+        );
+        assert_eq!(
+            parse("Wednesday, 31-Feb-00 08:49:37 GMT").unwrap(),
+            "Tue, 29 Feb 2000 08:49:37 GMT"
+        );
+        assert_eq!(
+            parse("Mon Feb 30 08:49:37 2100").unwrap(),
+            "Sun, 28 Feb 2100 08:49:37 GMT"
+        );
+        // Valid days must still have the right weekday.
+        assert!(parse("Mon, 28 Feb 2015 08:49:37 GMT")
+            .unwrap_err()
+            .is_invalid_date());
+        assert!(parse("Mon, 32 Jan 2015 08:49:37 GMT")
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(ParseOptions::new()
+            .parse("Thu, 31 Apr 2015 08:49:37 GMT")
+            .is_err());
+    }
+
+    #[test]
+    fn test_missing_comma() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let options = ParseOptions::new().missing_comma();
+        assert_eq!(options.parse(" Sun 06 Nov 1994 08:49:37 GMT "), Ok(d));
+        assert_eq!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT"), Ok(d));
+        assert_eq!(options.parse("Sun Nov  6 08:49:37 1994"), Ok(d));
+        assert!(options
+            .parse("Mon 06 Nov 1994 08:49:37 GMT")
+            .unwrap_err()
+            .is_invalid_date());
+        assert!(options.parse("Sun  06 Nov 1994 08:49:37 GMT").is_err());
+        assert!(ParseOptions::new()
+            .parse("Sun 06 Nov 1994 08:49:37 GMT")
+            .is_err());
+        let options = options.zone_offset();
+        assert_eq!(options.parse("Sun 06 Nov 1994 09:49:37 GMT+0100"), Ok(d));
+    }
+
+    #[cfg(feature = "detailed-errors")]
+    #[test]
+    fn test_missing_comma_offset() {
+        let options = ParseOptions::new().missing_comma();
+        let e = options.parse(" Sun 06 Nov 1994 08:49:3x GMT").unwrap_err();
+        assert_eq!(e.offset(), Some(23));
+        let e = options.parse("Sun 06 Nov 1994 08:49:37 GMT ").map(|_| ());
+        assert!(e.is_ok());
+    }
+
+    #[test]
+    fn test_short_time() {
+        let options = ParseOptions::new().short_time();
+        let parse = |s| options.parse(s).map(|d| d.to_string());
+        for s in &[
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 8:49:37 GMT",
+        ] {
+            assert_eq!(parse(s).unwrap(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        }
+        assert_eq!(
+            parse("Sun, 06 Nov 1994 0:0:0 GMT").unwrap(),
+            "Sun, 06 Nov 1994 00:00:00 GMT"
+        );
+        assert!(parse("Sun, 06 Nov 1994 8:49:337 GMT").is_err());
+        assert!(parse("Sun, 06 Nov 1994 :49:37 GMT").is_err());
+        assert!(parse("Sun, 06 Nov 1994 8:60:37 GMT")
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(parse("Sun, 6 Nov 1994 08:49:37 GMT").is_err());
+        assert!(parse("Sun 06 Nov 1994 8:49:37 GMT").is_err());
+        assert!(ParseOptions::new()
+            .parse("Sun, 06 Nov 1994 8:49:37 GMT")
+            .is_err());
+        let options = options.missing_comma();
+        assert!(options.parse("Sun 06 Nov 1994 8:49:37 GMT").is_ok());
+    }
+
+    #[test]
+    fn test_short_day() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let options = ParseOptions::new().short_day();
+        assert_eq!(options.parse("Sun, 6 Nov 1994 08:49:37 GMT"), Ok(d));
+        assert_eq!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT"), Ok(d));
+        assert!(options.parse("Sun, 006 Nov 1994 08:49:37 GMT").is_err());
+        assert!(options.parse("Sun,  6 Nov 1994 08:49:37 GMT").is_err());
+        assert!(options.parse("Sun, 6 Nov 1994 8:49:37 GMT").is_err());
+        assert!(ParseOptions::new()
+            .parse("Sun, 6 Nov 1994 08:49:37 GMT")
+            .is_err());
+    }
+
+    #[test]
+    fn test_numeric_month() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let options = ParseOptions::new().numeric_month();
+        assert_eq!(options.parse("Sun, 06 11 1994 08:49:37 GMT"), Ok(d));
+        assert_eq!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT"), Ok(d));
+        assert!(options
+            .parse("Sun, 06 13 1994 08:49:37 GMT")
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(options.parse("Sun, 06 00 1994 08:49:37 GMT").is_err());
+        assert!(options.parse("Sun, 06 1 1994 08:49:37 GMT").is_err());
+        assert!(options.parse("Sun, 06 011 1994 08:49:37 GMT").is_err());
+        assert!(ParseOptions::new()
+            .parse("Sun, 06 11 1994 08:49:37 GMT")
+            .is_err());
+    }
+
+    #[test]
+    fn test_lenient_http_date() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        for s in &[
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+            "Sun 06 11 1994 8:49:37 GMT-0000",
+        ] {
+            let lenient: LenientHttpDate = s.parse().unwrap();
+            assert_eq!(*lenient, d);
+            assert_eq!(HttpDate::from(lenient), d);
+        }
+        let clamped: LenientHttpDate = "Thu, 31 Apr 2015 08:49:37 GMT".parse().unwrap();
+        assert_eq!(clamped.to_string(), "Thu, 30 Apr 2015 08:49:37 GMT");
+        assert!("soon".parse::<LenientHttpDate>().is_err());
+    }
+
+    #[test]
+    fn test_zone_offset() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let options = ParseOptions::new().zone_offset();
+        assert_eq!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT+0000 "), Ok(d));
+        assert_eq!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT-0000"), Ok(d));
+        assert_eq!(options.parse("Sun, 06 Nov 1994 01:49:37 GMT-0700"), Ok(d));
+        assert_eq!(options.parse("Sunday, 06-Nov-94 14:19:37 GMT+0530"), Ok(d));
+        assert_eq!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT"), Ok(d));
+        assert!(options
+            .parse("Thu, 01 Jan 1970 00:30:00 GMT+0100")
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT+2400").is_err());
+        assert!(options.parse("Sun, 06 Nov 1994 08:49:37 UTC+0000").is_err());
+        assert!(options.parse("Sun, 06 Nov 1994 08:49:37 GMT+000").is_err());
+        assert!(options
+            .parse_bytes(b"Sun, 06 Nov 1994 08:49:37 GMT+0000\xa0")
+            .is_err());
+        assert!(ParseOptions::new()
+            .parse("Sun, 06 Nov 1994 08:49:37 GMT+0000")
+            .is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Proof harnesses for the Kani model checker, run with `cargo kani`.
+
+use crate::{HttpDate, IMF_FIXDATE_LEN};
+
+/// Parsing never panics, whatever the input.
+#[kani::proof]
+#[kani::unwind(34)]
+fn parse_never_panics() {
+    let bytes: [u8; 32] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(len <= bytes.len());
+    let _ = HttpDate::parse_bytes(&bytes[..len]);
+}
+
+/// Formatting an IMF-fixdate that parsed gives back the input.
+#[kani::proof]
+#[kani::unwind(31)]
+fn imf_fixdate_round_trips() {
+    let bytes: [u8; IMF_FIXDATE_LEN] = kani::any();
+    if let Ok(date) = HttpDate::parse_imf_fixdate_bytes(&bytes) {
+        let mut buf = [0; IMF_FIXDATE_LEN];
+        assert_eq!(date.format_into(&mut buf), &bytes);
+    }
+}
+
+/// Every date survives formatting and parsing.
+#[kani::proof]
+#[kani::unwind(31)]
+fn format_parse_round_trips() {
+    let secs: u64 = kani::any();
+    if let Some(date) = HttpDate::from_secs_since_epoch(secs) {
+        let mut buf = [0; IMF_FIXDATE_LEN];
+        let s = date.format_into(&mut buf);
+        assert_eq!(HttpDate::parse_bytes(s), Ok(date));
+    }
+}
+
+/// Formatting a date in any accepted format gives the normalized input.
+#[cfg(feature = "std")]
+#[kani::proof]
+#[kani::unwind(34)]
+fn format_equals_normalize() {
+    let bytes: [u8; 32] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(len <= bytes.len());
+    let s = match core::str::from_utf8(&bytes[..len]) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    if let Ok(date) = HttpDate::parse_bytes(s.as_bytes()) {
+        kani::cover!(len == 30, "RFC 850 date with the shortest weekday");
+        kani::cover!(len == 24 && bytes[3] == b' ', "asctime date");
+        let mut buf = [0; IMF_FIXDATE_LEN];
+        let normalized = crate::normalize_http_date(s).unwrap();
+        assert_eq!(&date.format_into(&mut buf)[..], normalized.as_bytes());
+    }
+}
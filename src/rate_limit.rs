@@ -0,0 +1,110 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use crate::error::ErrorKind;
+use crate::{Error, HttpDate};
+
+/// Numbers at least this large are seconds since the epoch, smaller ones
+/// are delays. The threshold is September 9, 2001.
+const EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+/// Value of the `RateLimit-Reset` and `X-RateLimit-Reset` header fields.
+///
+/// Servers send the time the rate limit resets either as a number of
+/// seconds to wait, as seconds since the epoch (e.g. GitHub) or as an
+/// HTTP date. Numbers of at least 10<sup>9</sup> are read as seconds since
+/// the epoch, as a delay of more than 31 years is not plausible.
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use httpdate::RateLimitReset;
+/// assert_eq!("60".parse(), Ok(RateLimitReset::Delay(Duration::from_secs(60))));
+/// let reset: RateLimitReset = "1372700873".parse().unwrap();
+/// assert_eq!(
+///     reset.resolve(UNIX_EPOCH),
+///     UNIX_EPOCH + Duration::from_secs(1372700873)
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum RateLimitReset {
+    /// The limit resets after waiting this long, in whole seconds.
+    Delay(Duration),
+    /// The limit resets at this date.
+    Date(HttpDate),
+}
+
+impl RateLimitReset {
+    /// The time the limit resets, given the time the response was received.
+    pub fn resolve(&self, now: SystemTime) -> SystemTime {
+        match *self {
+            RateLimitReset::Delay(delay) => now + delay,
+            RateLimitReset::Date(date) => date.into(),
+        }
+    }
+}
+
+impl FromStr for RateLimitReset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<RateLimitReset, Error> {
+        let t = s.trim();
+        if !t.bytes().next().is_some_and(|b| b.is_ascii_digit()) {
+            return s.parse().map(RateLimitReset::Date);
+        }
+        if let Some(pos) = t.bytes().position(|b| !b.is_ascii_digit()) {
+            return Err(Error::format(pos));
+        }
+        let secs: u64 = t.parse().map_err(|_| Error::new(ErrorKind::Range, None))?;
+        if secs < EPOCH_THRESHOLD {
+            Ok(RateLimitReset::Delay(Duration::from_secs(secs)))
+        } else {
+            HttpDate::from_secs_since_epoch(secs)
+                .map(RateLimitReset::Date)
+                .ok_or_else(|| Error::new(ErrorKind::Range, None))
+        }
+    }
+}
+
+impl Display for RateLimitReset {
+    /// Formats a delay as seconds and a date as IMF-fixdate.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RateLimitReset::Delay(delay) => write!(f, "{}", delay.as_secs()),
+            RateLimitReset::Date(date) => write!(f, "{}", date),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::RateLimitReset;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_rate_limit_reset() {
+        let now = UNIX_EPOCH + Duration::from_secs(784111777);
+        let delay: RateLimitReset = " 999999999 ".parse().unwrap();
+        assert_eq!(delay, RateLimitReset::Delay(Duration::from_secs(999999999)));
+        assert_eq!(delay.resolve(now), now + Duration::from_secs(999999999));
+
+        let epoch: RateLimitReset = "1000000000".parse().unwrap();
+        assert_eq!(
+            epoch.resolve(now),
+            UNIX_EPOCH + Duration::from_secs(1000000000)
+        );
+        assert_eq!(epoch.to_string(), "Sun, 09 Sep 2001 01:46:40 GMT");
+
+        let date: RateLimitReset = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert_eq!(date, RateLimitReset::Date(HttpDate::from(now)));
+
+        assert!("99999999999999999999999"
+            .parse::<RateLimitReset>()
+            .unwrap_err()
+            .is_out_of_range());
+        assert!("1.5".parse::<RateLimitReset>().is_err());
+        assert!("".parse::<RateLimitReset>().is_err());
+    }
+}
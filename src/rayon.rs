@@ -0,0 +1,72 @@
+//! Parallel bulk parsing and formatting, enabled with the `rayon` feature.
+
+use ::rayon::prelude::*;
+
+use crate::{fmt_http_dates, Error, HttpDate};
+
+/// Dates formatted by one task.
+const CHUNK_LEN: usize = 4096;
+
+/// Parse many dates from HTTP header fields in parallel.
+///
+/// Collecting the results into a `Vec` keeps their order.
+///
+/// ```
+/// use rayon::prelude::*;
+///
+/// let dates = vec!["Sun, 06 Nov 1994 08:49:37 GMT"; 1000];
+/// let parsed: Vec<_> = httpdate::par_parse_http_dates(dates).collect();
+/// assert!(parsed.iter().all(|d| d.is_ok()));
+/// ```
+pub fn par_parse_http_dates<'a, I>(
+    dates: I,
+) -> impl ParallelIterator<Item = Result<HttpDate, Error>> + 'a
+where
+    I: IntoParallelIterator<Item = &'a str>,
+    I::Iter: 'a,
+{
+    dates
+        .into_par_iter()
+        .map(|s| HttpDate::parse_bytes(s.as_bytes()))
+}
+
+/// Format many dates into one string in parallel, separated by `separator`.
+///
+/// The output is the same as from [`fmt_http_dates`].
+pub fn par_fmt_http_dates(dates: &[HttpDate], separator: &str) -> String {
+    let chunks: Vec<String> = dates
+        .par_chunks(CHUNK_LEN)
+        .map(|chunk| {
+            let mut out = String::new();
+            fmt_http_dates(chunk.iter().copied(), separator, &mut out);
+            out
+        })
+        .collect();
+    chunks.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use ::rayon::prelude::*;
+
+    use super::{par_fmt_http_dates, par_parse_http_dates};
+    use crate::{fmt_http_dates, HttpDate};
+
+    #[test]
+    fn test_par_round_trip() {
+        let dates: Vec<HttpDate> = (0..10_000)
+            .map(|i| HttpDate::from(UNIX_EPOCH + Duration::from_secs(i * 86399)))
+            .collect();
+        let formatted = par_fmt_http_dates(&dates, "\n");
+        let mut expected = String::new();
+        fmt_http_dates(dates.iter().copied(), "\n", &mut expected);
+        assert_eq!(formatted, expected);
+
+        let lines: Vec<&str> = formatted.lines().collect();
+        let parsed: Vec<HttpDate> = par_parse_http_dates(lines).map(Result::unwrap).collect();
+        assert_eq!(parsed, dates);
+        assert_eq!(par_fmt_http_dates(&[], ", "), "");
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use crate::{delta_seconds, Error, HttpDate};
+
+/// Value of the `Retry-After` header field.
+///
+/// The field contains either a number of seconds to wait or an HTTP date,
+/// see RFC 9110 section 10.2.3. Delays larger than 2<sup>31</sup> seconds
+/// are clamped.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use httpdate::RetryAfter;
+/// assert_eq!("120".parse(), Ok(RetryAfter::Delay(Duration::from_secs(120))));
+/// assert!(matches!(
+///     "Fri, 31 Dec 1999 23:59:59 GMT".parse(),
+///     Ok(RetryAfter::Date(_))
+/// ));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum RetryAfter {
+    /// Retry after waiting this long, in whole seconds.
+    Delay(Duration),
+    /// Retry after this date.
+    Date(HttpDate),
+}
+
+impl RetryAfter {
+    /// The time after which to retry, given the time the response was received.
+    ///
+    /// Delays are clamped to [`delta_seconds::MAX`] like parsed ones, and a
+    /// time past what `SystemTime` can represent saturates to the later of
+    /// `now` and [`HttpDate::MAX`] instead of panicking.
+    pub fn resolve(&self, now: SystemTime) -> SystemTime {
+        match *self {
+            RetryAfter::Delay(delay) => now
+                .checked_add(delay.min(delta_seconds::MAX))
+                .unwrap_or_else(|| now.max(HttpDate::MAX.into())),
+            RetryAfter::Date(date) => date.into(),
+        }
+    }
+}
+
+impl FromStr for RetryAfter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<RetryAfter, Error> {
+        let t = s.trim();
+        if t.bytes().next().is_some_and(|b| b.is_ascii_digit()) {
+            delta_seconds::parse_secs(t.as_bytes())
+                .map(|secs| RetryAfter::Delay(Duration::from_secs(secs)))
+        } else {
+            s.parse().map(RetryAfter::Date)
+        }
+    }
+}
+
+impl Display for RetryAfter {
+    /// Formats a delay as seconds and a date as IMF-fixdate.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RetryAfter::Delay(delay) => write!(f, "{}", delay.as_secs()),
+            RetryAfter::Date(date) => write!(f, "{}", date),
+        }
+    }
+}
+
+impl From<HttpDate> for RetryAfter {
+    fn from(v: HttpDate) -> RetryAfter {
+        RetryAfter::Date(v)
+    }
+}
+
+impl From<Duration> for RetryAfter {
+    fn from(v: Duration) -> RetryAfter {
+        RetryAfter::Delay(v)
+    }
+}
+
+/// Generate `Retry-After` values with random jitter.
+///
+/// Adding a random number of seconds to the delay keeps clients that were
+/// rejected at the same time from retrying at the same time.
+///
+/// ```
+/// # use std::time::{Duration, SystemTime};
+/// # use httpdate::{RetryAfter, RetryAfterBuilder};
+/// let builder = RetryAfterBuilder::new(Duration::from_secs(60)).jitter(Duration::from_secs(30));
+/// match builder.build(SystemTime::now()) {
+///     RetryAfter::Delay(delay) => assert!((60..=90).contains(&delay.as_secs())),
+///     RetryAfter::Date(_) => unreachable!(),
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RetryAfterBuilder {
+    base: Duration,
+    jitter: Duration,
+    absolute: bool,
+}
+
+impl RetryAfterBuilder {
+    /// Retry after `base` without jitter.
+    pub fn new(base: Duration) -> RetryAfterBuilder {
+        RetryAfterBuilder {
+            base,
+            jitter: Duration::from_secs(0),
+            absolute: false,
+        }
+    }
+
+    /// Add up to `max` whole seconds of random jitter to the delay.
+    pub fn jitter(mut self, max: Duration) -> RetryAfterBuilder {
+        self.jitter = max;
+        self
+    }
+
+    /// Generate an HTTP date instead of a number of seconds.
+    pub fn absolute(mut self, absolute: bool) -> RetryAfterBuilder {
+        self.absolute = absolute;
+        self
+    }
+
+    /// Generate a value for a response sent at `now`.
+    pub fn build(&self, now: SystemTime) -> RetryAfter {
+        let random = RandomState::new().build_hasher().finish();
+        self.build_with(now, random)
+    }
+
+    /// Like [`RetryAfterBuilder::build`] but with the jitter taken from
+    /// the given random number instead of the standard library.
+    ///
+    /// Delays are clamped to [`delta_seconds::MAX`] and dates after the
+    /// year 9999 to [`HttpDate::MAX`].
+    pub fn build_with(&self, now: SystemTime, random: u64) -> RetryAfter {
+        let jitter = match self.jitter.as_secs() {
+            0 => 0,
+            max => random % (max.saturating_add(1)),
+        };
+        let delay =
+            Duration::from_secs(self.base.as_secs().saturating_add(jitter)).min(delta_seconds::MAX);
+        if self.absolute {
+            let date = now
+                .checked_add(delay)
+                .map_or(HttpDate::MAX, HttpDate::from_system_time_saturating);
+            RetryAfter::Date(date)
+        } else {
+            RetryAfter::Delay(delay)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::{RetryAfter, RetryAfterBuilder};
+    use crate::{delta_seconds, HttpDate};
+
+    #[test]
+    fn test_retry_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(784111777);
+        let delay: RetryAfter = " 120 ".parse().unwrap();
+        assert_eq!(delay, RetryAfter::Delay(Duration::from_secs(120)));
+        assert_eq!(delay.resolve(now), now + Duration::from_secs(120));
+        assert_eq!(delay.to_string(), "120");
+
+        let date: RetryAfter = "Sunday, 06-Nov-94 08:49:37 GMT".parse().unwrap();
+        assert_eq!(date, RetryAfter::Date(HttpDate::from(now)));
+        assert_eq!(date.resolve(UNIX_EPOCH), now);
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let huge: RetryAfter = "99999999999999999999999".parse().unwrap();
+        assert_eq!(huge, RetryAfter::Delay(Duration::from_secs(1 << 31)));
+        assert_eq!(
+            RetryAfter::Delay(Duration::MAX).resolve(now),
+            now + delta_seconds::MAX
+        );
+        assert!("12a".parse::<RetryAfter>().is_err());
+        assert!("-1".parse::<RetryAfter>().is_err());
+        assert!("".parse::<RetryAfter>().is_err());
+    }
+
+    #[test]
+    fn test_builder() {
+        let now = UNIX_EPOCH + Duration::from_secs(784111777);
+        let builder = RetryAfterBuilder::new(Duration::from_millis(120_500));
+        assert_eq!(
+            builder.build_with(now, 7),
+            RetryAfter::Delay(Duration::from_secs(120))
+        );
+        let builder = builder.jitter(Duration::from_secs(10));
+        assert_eq!(
+            builder.build_with(now, 7),
+            RetryAfter::Delay(Duration::from_secs(127))
+        );
+        assert_eq!(
+            builder.build_with(now, 21),
+            RetryAfter::Delay(Duration::from_secs(130))
+        );
+        let date = builder.absolute(true).build_with(now, 0);
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:51:37 GMT");
+        for _ in 0..10 {
+            assert!(builder.build(now).resolve(now) <= now + Duration::from_secs(130));
+        }
+    }
+
+    #[test]
+    fn test_builder_clamps() {
+        let now = UNIX_EPOCH + Duration::from_secs(784111777);
+        let builder = RetryAfterBuilder::new(Duration::from_secs(u64::MAX))
+            .jitter(Duration::from_secs(u64::MAX));
+        let delay = builder.build_with(now, u64::MAX - 1);
+        assert_eq!(delay, RetryAfter::Delay(Duration::from_secs(1 << 31)));
+        assert_eq!(delay.to_string(), "2147483648");
+        let date = builder.absolute(true).build_with(now, u64::MAX - 1);
+        assert_eq!(date.to_string(), "Fri, 24 Nov 2062 12:03:45 GMT");
+        let late = SystemTime::from(HttpDate::MAX);
+        assert_eq!(
+            builder.absolute(true).build_with(late, 0),
+            RetryAfter::Date(HttpDate::MAX)
+        );
+    }
+}
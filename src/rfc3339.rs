@@ -0,0 +1,159 @@
+use core::fmt::{self, Display, Formatter};
+
+use crate::calendar::{days_from_civil, month_len};
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+/// Formats a date as RFC 3339 timestamp: `1994-11-06T08:49:37Z`.
+pub(crate) struct Rfc3339(pub(crate) HttpDate);
+
+impl Display for Rfc3339 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let d = self.0.fields();
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            d.year, d.mon, d.day, d.hour, d.min, d.sec
+        )
+    }
+}
+
+impl HttpDate {
+    /// Parse an RFC 3339 timestamp like `1994-11-06T08:49:37Z`.
+    ///
+    /// Fractional seconds are truncated and offsets are applied.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::parse_rfc3339("1994-11-06T09:49:37.5+01:00").unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// assert_eq!(date.rfc3339().to_string(), "1994-11-06T08:49:37Z");
+    /// ```
+    pub fn parse_rfc3339(s: &str) -> Result<HttpDate, Error> {
+        parse(s)
+    }
+
+    /// Display the date as RFC 3339 timestamp in UTC.
+    pub fn rfc3339(&self) -> impl Display {
+        Rfc3339(*self)
+    }
+}
+
+/// Parse an RFC 3339 timestamp like `1994-11-06T08:49:37Z`.
+///
+/// Fractional seconds are truncated and offsets are applied.
+pub(crate) fn parse(s: &str) -> Result<HttpDate, Error> {
+    // Example: `1994-11-06T08:49:37.123+02:00`
+    let s = s.as_bytes();
+    if s.len() < 20 {
+        return Err(Error::format(s.len()));
+    }
+    for &(at, c) in &[(4, b'-'), (7, b'-'), (13, b':'), (16, b':')] {
+        if s[at] != c {
+            return Err(Error::format(at));
+        }
+    }
+    if !matches!(s[10], b'T' | b't' | b' ') {
+        return Err(Error::format(10));
+    }
+    let year = num(s, 0, 4)?;
+    let mon = num(s, 5, 2)?;
+    let day = num(s, 8, 2)?;
+    let hour = num(s, 11, 2)?;
+    let min = num(s, 14, 2)?;
+    let sec = num(s, 17, 2)?;
+
+    let mut rest = 19;
+    if s[rest] == b'.' {
+        rest += 1;
+        let digits = s[rest..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(Error::format(rest));
+        }
+        rest += digits;
+    }
+    let offset = match s.get(rest) {
+        Some(b'Z') | Some(b'z') if s.len() == rest + 1 => 0,
+        Some(&sign @ b'+') | Some(&sign @ b'-') if s.len() == rest + 6 => {
+            if s[rest + 3] != b':' {
+                return Err(Error::format(rest + 3));
+            }
+            let off_hour = num(s, rest + 1, 2)?;
+            let off_min = num(s, rest + 4, 2)?;
+            if off_hour > 23 || off_min > 59 {
+                return Err(Error::new(ErrorKind::Range, Some(rest)));
+            }
+            let offset = off_hour * 3600 + off_min * 60;
+            if sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return Err(Error::format(rest)),
+    };
+
+    from_local(year, mon, day, hour, min, sec, offset)
+}
+
+/// A date from local time fields and the offset of the time zone in seconds.
+pub(crate) fn from_local(
+    year: i64,
+    mon: i64,
+    day: i64,
+    hour: i64,
+    min: i64,
+    sec: i64,
+    offset: i64,
+) -> Result<HttpDate, Error> {
+    if !(1..=12).contains(&mon) || hour > 23 || min > 59 || sec > 59 {
+        return Err(Error::new(ErrorKind::Range, None));
+    }
+    if day < 1 || day > i64::from(month_len(year as u16, mon as u8)) {
+        return Err(Error::new(ErrorKind::Calendar, None));
+    }
+    let secs = days_from_civil(year, mon, day) * 86400 + hour * 3600 + min * 60 + sec - offset;
+    if secs < 0 {
+        return Err(Error::new(ErrorKind::Range, None));
+    }
+    HttpDate::from_secs_since_epoch(secs as u64).ok_or_else(|| Error::new(ErrorKind::Range, None))
+}
+
+pub(crate) fn num(s: &[u8], at: usize, len: usize) -> Result<i64, Error> {
+    let mut n = 0;
+    for &b in &s[at..at + len] {
+        if !b.is_ascii_digit() {
+            return Err(Error::format(at));
+        }
+        n = n * 10 + i64::from(b - b'0');
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{parse, Rfc3339};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_rfc3339() {
+        let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        assert_eq!(Rfc3339(d).to_string(), "1994-11-06T08:49:37Z");
+        assert_eq!(parse("1994-11-06T08:49:37Z"), Ok(d));
+        assert_eq!(parse("1994-11-06t08:49:37.999z"), Ok(d));
+        assert_eq!(parse("1994-11-06T10:19:37+01:30"), Ok(d));
+        assert_eq!(parse("1994-11-05T23:49:37-09:00"), Ok(d));
+        assert_eq!(
+            parse("1969-12-31T23:00:00-01:00"),
+            Ok(HttpDate::from(UNIX_EPOCH))
+        );
+        assert!(parse("1969-12-31T23:59:59Z").unwrap_err().is_out_of_range());
+        assert!(parse("1994-02-29T08:49:37Z").unwrap_err().is_invalid_date());
+        assert!(parse("1994-11-06T08:49:37").is_err());
+        assert!(parse("1994-11-06T08:49:37.Z").is_err());
+        assert!(parse("Sun, 06 Nov 1994 08:49:37 GMT").is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use core::iter;
+
+use crate::{HttpDate, IMF_FIXDATE_LEN};
+
+/// Lengths of RFC 850 dates, they depend on the length of the weekday name.
+const RFC850_LENS: [usize; 4] = [30, 31, 32, 33];
+
+/// Find all IMF-fixdate and RFC 850 dates in a byte string.
+///
+/// Returns the offset of each date and the parsed date. Only dates ending
+/// in ` GMT` are found, so asctime dates are skipped. The scanner looks
+/// for the letter `G` and only then tries to parse the bytes before it,
+/// so it is fast for large inputs like HAR files or captured traffic.
+///
+/// ```
+/// let headers = b"HTTP/1.1 200 OK\r\nDate: Sun, 06 Nov 1994 08:49:37 GMT\r\n\
+///     Expires: Sunday, 06-Nov-94 09:49:37 GMT\r\n\r\n";
+/// let dates: Vec<_> = httpdate::find_dates(headers).map(|(at, _)| at).collect();
+/// assert_eq!(dates, [23, 63]);
+/// ```
+pub fn find_dates(haystack: &[u8]) -> impl Iterator<Item = (usize, HttpDate)> + '_ {
+    let mut pos = 0;
+    iter::from_fn(move || {
+        while let Some(g) = haystack[pos..].iter().position(|&b| b == b'G') {
+            let g = pos + g;
+            pos = g + 1;
+            if g == 0 || haystack[g - 1] != b' ' || haystack.get(g + 1..g + 3) != Some(b"MT") {
+                continue;
+            }
+            let end = g + 3;
+            for len in iter::once(IMF_FIXDATE_LEN).chain(RFC850_LENS.iter().copied()) {
+                if len > end {
+                    break;
+                }
+                let start = end - len;
+                if let Some(date) = HttpDate::parse_exact(&haystack[start..end]) {
+                    pos = end;
+                    return Some((start, date));
+                }
+            }
+        }
+        pos = haystack.len();
+        None
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use super::find_dates;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_find_dates() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let found = |s: &[u8]| find_dates(s).collect::<Vec<_>>();
+        assert_eq!(found(b"Sun, 06 Nov 1994 08:49:37 GMT"), [(0, d)]);
+        assert_eq!(
+            found(b"xSun, 06 Nov 1994 08:49:37 GMTSunday, 06-Nov-94 08:49:37 GMT"),
+            [(1, d), (30, d)]
+        );
+        assert_eq!(found(b"Wednesday, 09-Nov-94 08:49:37 GMT")[0].0, 0);
+        assert_eq!(found(b"Sun Nov  6 08:49:37 1994 GMT GMT G"), []);
+        assert_eq!(found(b"Sun, 07 Nov 1994 08:49:37 GMT"), []);
+        assert_eq!(found(b""), []);
+    }
+}
@@ -0,0 +1,385 @@
+//! Serde support, enabled with the `serde` feature.
+//!
+//! For human-readable formats like JSON `HttpDate` is serialized as an
+//! IMF-fixdate string like `Sun, 06 Nov 1994 08:49:37 GMT`. Other formats
+//! store the seconds since the Unix epoch as an integer.
+//!
+//! Human-readable deserializers accept a string in any of the formats
+//! supported by the `FromStr` impl or an integer. Binary formats expect an
+//! integer.
+//!
+//! To pick the representation of a single field use one of the helper
+//! modules with `#[serde(with = "...")]`. They work for both `HttpDate`
+//! and `SystemTime` fields.
+//!
+//! * [`imf_fixdate`] as `"Sun, 06 Nov 1994 08:49:37 GMT"`
+//! * [`timestamp`] as `784111777`
+//! * [`rfc3339`] as `"1994-11-06T08:49:37Z"`
+//!
+//! Each module has an `option` submodule for `Option` fields like
+//! `httpdate::serde::imf_fixdate::option`.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use std::time::SystemTime;
+//!
+//! use httpdate::HttpDate;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct CacheEntry {
+//!     #[serde(with = "httpdate::serde::imf_fixdate")]
+//!     last_modified: HttpDate,
+//!     #[serde(with = "httpdate::serde::timestamp")]
+//!     stored_at: SystemTime,
+//! }
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt::{self, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ::serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use ::serde::ser::{self, Serialize, Serializer};
+
+use crate::HttpDate;
+
+/// Date types supported by the helper modules.
+///
+/// This trait is implemented for `HttpDate` and `SystemTime`. A
+/// `SystemTime` is truncated to whole seconds and must be between
+/// the epoch and year 10000.
+pub trait SerdeDate: Sized + sealed::Sealed {
+    #[doc(hidden)]
+    fn to_http_date(&self) -> Option<HttpDate>;
+    #[doc(hidden)]
+    fn from_http_date(date: HttpDate) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::HttpDate {}
+    impl Sealed for std::time::SystemTime {}
+}
+
+impl SerdeDate for HttpDate {
+    fn to_http_date(&self) -> Option<HttpDate> {
+        Some(*self)
+    }
+
+    fn from_http_date(date: HttpDate) -> HttpDate {
+        date
+    }
+}
+
+impl SerdeDate for SystemTime {
+    fn to_http_date(&self) -> Option<HttpDate> {
+        let dur = self.duration_since(UNIX_EPOCH).ok()?;
+        HttpDate::from_secs_since_epoch(dur.as_secs())
+    }
+
+    fn from_http_date(date: HttpDate) -> SystemTime {
+        date.into()
+    }
+}
+
+fn to_http_date<T: SerdeDate, E: ser::Error>(value: &T) -> Result<HttpDate, E> {
+    value
+        .to_http_date()
+        .ok_or_else(|| E::custom("date must be between 1970 and 9999"))
+}
+
+impl Serialize for HttpDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.secs_since_epoch())
+        }
+    }
+}
+
+struct HttpDateVisitor;
+
+impl<'de> Visitor<'de> for HttpDateVisitor {
+    type Value = HttpDate;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an HTTP date or seconds since the epoch")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<HttpDate, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<HttpDate, E> {
+        HttpDate::from_secs_since_epoch(v)
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<HttpDate, E> {
+        u64::try_from(v)
+            .ok()
+            .and_then(HttpDate::from_secs_since_epoch)
+            .ok_or_else(|| E::invalid_value(Unexpected::Signed(v), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for HttpDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<HttpDate, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HttpDateVisitor)
+        } else {
+            deserializer.deserialize_u64(HttpDateVisitor)
+        }
+    }
+}
+
+/// Adds an `option` submodule for `Option<T>` fields to a helper module.
+macro_rules! option_module {
+    () => {
+        /// (De)serialize an optional date, `None` is represented as `null`.
+        ///
+        /// Add `#[serde(default)]` to the field to also accept a missing value.
+        pub mod option {
+            use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            use crate::serde::SerdeDate;
+
+            struct Ser<'a, T>(&'a T);
+
+            impl<'a, T: SerdeDate> Serialize for Ser<'a, T> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    super::serialize(self.0, serializer)
+                }
+            }
+
+            struct De<T>(T);
+
+            impl<'de, T: SerdeDate> Deserialize<'de> for De<T> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    super::deserialize(deserializer).map(De)
+                }
+            }
+
+            /// Serialize an optional date.
+            pub fn serialize<T: SerdeDate, S: Serializer>(
+                value: &Option<T>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                match value {
+                    Some(value) => serializer.serialize_some(&Ser(value)),
+                    None => serializer.serialize_none(),
+                }
+            }
+
+            /// Deserialize an optional date.
+            pub fn deserialize<'de, T: SerdeDate, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<T>, D::Error> {
+                Option::<De<T>>::deserialize(deserializer).map(|v| v.map(|De(v)| v))
+            }
+        }
+    };
+}
+
+struct Rfc3339Visitor;
+
+impl<'de> Visitor<'de> for Rfc3339Visitor {
+    type Value = HttpDate;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an RFC 3339 timestamp")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<HttpDate, E> {
+        crate::rfc3339::parse(v).map_err(E::custom)
+    }
+}
+
+/// (De)serialize as IMF-fixdate string: `"Sun, 06 Nov 1994 08:49:37 GMT"`.
+///
+/// All HTTP date formats are accepted when deserializing.
+pub mod imf_fixdate {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::{to_http_date, HttpDateVisitor, SerdeDate};
+
+    /// Serialize a date as IMF-fixdate string.
+    pub fn serialize<T: SerdeDate, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&to_http_date(value)?)
+    }
+
+    /// Deserialize a date from an HTTP date string.
+    pub fn deserialize<'de, T: SerdeDate, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer
+            .deserialize_str(HttpDateVisitor)
+            .map(T::from_http_date)
+    }
+
+    option_module!();
+}
+
+/// (De)serialize as integer seconds since the Unix epoch: `784111777`.
+pub mod timestamp {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::{to_http_date, HttpDateVisitor, SerdeDate};
+
+    /// Serialize a date as seconds since the epoch.
+    pub fn serialize<T: SerdeDate, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(to_http_date(value)?.secs_since_epoch())
+    }
+
+    /// Deserialize a date from seconds since the epoch.
+    pub fn deserialize<'de, T: SerdeDate, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer
+            .deserialize_u64(HttpDateVisitor)
+            .map(T::from_http_date)
+    }
+
+    option_module!();
+}
+
+/// (De)serialize as RFC 3339 string: `"1994-11-06T08:49:37Z"`.
+///
+/// When deserializing fractional seconds are truncated and
+/// offsets other than `Z` are converted to UTC.
+pub mod rfc3339 {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::{to_http_date, Rfc3339Visitor, SerdeDate};
+    use crate::rfc3339::Rfc3339;
+
+    /// Serialize a date as RFC 3339 string.
+    pub fn serialize<T: SerdeDate, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Rfc3339(to_http_date(value)?))
+    }
+
+    /// Deserialize a date from an RFC 3339 string.
+    pub fn deserialize<'de, T: SerdeDate, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer
+            .deserialize_str(Rfc3339Visitor)
+            .map(T::from_http_date)
+    }
+
+    option_module!();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde_derive::{Deserialize, Serialize};
+
+    use crate::HttpDate;
+
+    #[test]
+    fn test_json_round_trip() {
+        let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, "\"Sun, 06 Nov 1994 08:49:37 GMT\"");
+        assert_eq!(serde_json::from_str::<HttpDate>(&json).unwrap(), d);
+        let legacy = "\"Sunday, 06-Nov-94 08:49:37 GMT\"";
+        assert_eq!(serde_json::from_str::<HttpDate>(legacy).unwrap(), d);
+        assert_eq!(serde_json::from_str::<HttpDate>("784111777").unwrap(), d);
+        assert!(serde_json::from_str::<HttpDate>("\"yesterday\"").is_err());
+        assert!(serde_json::from_str::<HttpDate>("-1").is_err());
+        assert!(serde_json::from_str::<HttpDate>("253402300800").is_err());
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        let d = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        let bytes = bincode::serialize(&d).unwrap();
+        assert_eq!(bytes, 784111777u64.to_le_bytes());
+        assert_eq!(bincode::deserialize::<HttpDate>(&bytes).unwrap(), d);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Fields {
+        #[serde(with = "crate::serde::imf_fixdate")]
+        a: SystemTime,
+        #[serde(with = "crate::serde::timestamp")]
+        b: HttpDate,
+        #[serde(with = "crate::serde::rfc3339")]
+        c: SystemTime,
+    }
+
+    #[test]
+    fn test_with_modules() {
+        let t = UNIX_EPOCH + Duration::from_secs(784111777);
+        let fields = Fields {
+            a: t,
+            b: t.into(),
+            c: t,
+        };
+        let json = serde_json::to_string(&fields).unwrap();
+        assert_eq!(
+            json,
+            r#"{"a":"Sun, 06 Nov 1994 08:49:37 GMT","b":784111777,"c":"1994-11-06T08:49:37Z"}"#
+        );
+        assert_eq!(serde_json::from_str::<Fields>(&json).unwrap(), fields);
+        let bytes = bincode::serialize(&fields).unwrap();
+        assert_eq!(bincode::deserialize::<Fields>(&bytes).unwrap(), fields);
+
+        let fields = Fields {
+            a: UNIX_EPOCH - Duration::from_secs(1),
+            b: t.into(),
+            c: t,
+        };
+        assert!(serde_json::to_string(&fields).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct OptionFields {
+        #[serde(with = "crate::serde::imf_fixdate::option")]
+        a: Option<HttpDate>,
+        #[serde(default, with = "crate::serde::timestamp::option")]
+        b: Option<SystemTime>,
+    }
+
+    #[test]
+    fn test_option_modules() {
+        let t = UNIX_EPOCH + Duration::from_secs(784111777);
+        let fields = OptionFields {
+            a: Some(t.into()),
+            b: Some(t),
+        };
+        let json = serde_json::to_string(&fields).unwrap();
+        assert_eq!(
+            json,
+            r#"{"a":"Sun, 06 Nov 1994 08:49:37 GMT","b":784111777}"#
+        );
+        assert_eq!(serde_json::from_str::<OptionFields>(&json).unwrap(), fields);
+
+        let fields = OptionFields { a: None, b: None };
+        let json = serde_json::to_string(&fields).unwrap();
+        assert_eq!(json, r#"{"a":null,"b":null}"#);
+        assert_eq!(serde_json::from_str::<OptionFields>(&json).unwrap(), fields);
+        assert_eq!(
+            serde_json::from_str::<OptionFields>(r#"{"a":null}"#).unwrap(),
+            fields
+        );
+        let bytes = bincode::serialize(&fields).unwrap();
+        assert_eq!(
+            bincode::deserialize::<OptionFields>(&bytes).unwrap(),
+            fields
+        );
+    }
+}
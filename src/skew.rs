@@ -0,0 +1,140 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{HttpDate, RetryAfter};
+
+/// Estimates the difference between the clock of a server and the local clock.
+///
+/// Feed it the `Date` header field of each response together with the
+/// local time the response was received. The offset is smoothed with an
+/// exponentially weighted moving average so single delayed responses
+/// don't disturb it much. Use the estimate to convert absolute times sent
+/// by the server like `Expires` or `Retry-After` to local time.
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use httpdate::{HttpDate, SkewEstimator};
+/// let received = UNIX_EPOCH + Duration::from_secs(784111777);
+/// // The server clock is one hour ahead.
+/// let date = HttpDate::from(received + Duration::from_secs(3600));
+/// let mut skew = SkewEstimator::new();
+/// skew.observe(date, received);
+/// assert_eq!(skew.offset(), Some(3600));
+/// assert_eq!(skew.to_local(date), received);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SkewEstimator {
+    /// Server time minus local time in seconds.
+    offset: Option<f64>,
+    weight: f64,
+}
+
+impl SkewEstimator {
+    /// Create an estimator giving each new observation a weight of 1/8.
+    pub fn new() -> SkewEstimator {
+        SkewEstimator::with_weight(0.125)
+    }
+
+    /// Create an estimator giving each new observation the given weight.
+    ///
+    /// A weight of 1 only uses the latest observation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is not greater than 0 and at most 1.
+    pub fn with_weight(weight: f64) -> SkewEstimator {
+        assert!(
+            weight > 0.0 && weight <= 1.0,
+            "weight must be in the range (0, 1]"
+        );
+        SkewEstimator {
+            offset: None,
+            weight,
+        }
+    }
+
+    /// Add the `Date` of a response and the local time it was received.
+    pub fn observe(&mut self, server_date: HttpDate, received: SystemTime) {
+        let local = match received.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs_f64(),
+            Err(e) => -e.duration().as_secs_f64(),
+        };
+        let sample = server_date.secs_since_epoch() as f64 - local;
+        self.offset = Some(match self.offset {
+            Some(offset) => offset + self.weight * (sample - offset),
+            None => sample,
+        });
+    }
+
+    /// The estimated offset in whole seconds, positive if the server clock is ahead.
+    ///
+    /// Returns `None` before the first observation.
+    pub fn offset(&self) -> Option<i64> {
+        self.offset.map(|offset| offset.round() as i64)
+    }
+
+    /// Convert a time from the server clock to the local clock.
+    pub fn to_local(&self, server_time: HttpDate) -> SystemTime {
+        let time = SystemTime::from(server_time);
+        match self.offset() {
+            Some(offset) if offset > 0 => time - Duration::from_secs(offset as u64),
+            Some(offset) => time + Duration::from_secs(offset.unsigned_abs()),
+            None => time,
+        }
+    }
+
+    /// Resolve a `Retry-After` value to local time.
+    ///
+    /// Delays are relative to `now` and need no correction,
+    /// dates are converted with [`SkewEstimator::to_local`].
+    pub fn resolve_retry_after(&self, retry_after: RetryAfter, now: SystemTime) -> SystemTime {
+        match retry_after {
+            RetryAfter::Delay(_) => retry_after.resolve(now),
+            RetryAfter::Date(date) => self.to_local(date),
+        }
+    }
+}
+
+impl Default for SkewEstimator {
+    fn default() -> SkewEstimator {
+        SkewEstimator::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::SkewEstimator;
+    use crate::{HttpDate, RetryAfter};
+
+    fn t(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_000_000 + secs)
+    }
+
+    #[test]
+    fn test_skew_estimator() {
+        let mut skew = SkewEstimator::with_weight(0.5);
+        assert_eq!(skew.offset(), None);
+        assert_eq!(skew.to_local(HttpDate::from(t(10))), t(10));
+
+        // The server is 100 seconds behind.
+        skew.observe(HttpDate::from(t(0)), t(100));
+        assert_eq!(skew.offset(), Some(-100));
+        assert_eq!(skew.to_local(HttpDate::from(t(50))), t(150));
+        // A delayed response moves the estimate only halfway.
+        skew.observe(HttpDate::from(t(100)), t(220));
+        assert_eq!(skew.offset(), Some(-110));
+
+        let now = t(300);
+        let delay = RetryAfter::Delay(Duration::from_secs(5));
+        assert_eq!(skew.resolve_retry_after(delay, now), t(305));
+        let date = RetryAfter::Date(HttpDate::from(t(200)));
+        assert_eq!(skew.resolve_retry_after(date, now), t(310));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_weight() {
+        SkewEstimator::with_weight(0.0);
+    }
+}
@@ -0,0 +1,126 @@
+//! Known good dates for conformance tests, enabled with the `test-util`
+//! feature.
+//!
+//! Run the vectors against a wrapper around this crate or against another
+//! implementation of HTTP dates:
+//!
+//! ```
+//! # use httpdate::HttpDate;
+//! for v in httpdate::vectors() {
+//!     let date: HttpDate = v.input.parse().unwrap();
+//!     assert_eq!(httpdate::UnixTimestamp::from(date).0, v.secs as i64);
+//! }
+//! ```
+
+/// The format of a test vector input.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// `Sun, 06 Nov 1994 08:49:37 GMT`, the preferred format.
+    ImfFixdate,
+    /// `Sunday, 06-Nov-94 08:49:37 GMT`, obsolete.
+    Rfc850,
+    /// `Sun Nov  6 08:49:37 1994`, obsolete.
+    Asctime,
+}
+
+/// A valid HTTP date and the time it stands for.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Vector {
+    /// The date as sent in a header field.
+    pub input: &'static str,
+    /// Seconds since the epoch.
+    pub secs: u64,
+    /// The format of the input.
+    pub format: Format,
+}
+
+const fn v(input: &'static str, secs: u64, format: Format) -> Vector {
+    Vector {
+        input,
+        secs,
+        format,
+    }
+}
+
+const VECTORS: &[Vector] = &[
+    // RFC 9110, section 5.6.7
+    v(
+        "Sun, 06 Nov 1994 08:49:37 GMT",
+        784111777,
+        Format::ImfFixdate,
+    ),
+    v("Sunday, 06-Nov-94 08:49:37 GMT", 784111777, Format::Rfc850),
+    v("Sun Nov  6 08:49:37 1994", 784111777, Format::Asctime),
+    // The epoch, often sent in `Expires` to mark a response as stale.
+    v("Thu, 01 Jan 1970 00:00:00 GMT", 0, Format::ImfFixdate),
+    v("Thursday, 01-Jan-70 00:00:00 GMT", 0, Format::Rfc850),
+    v("Thu Jan  1 00:00:00 1970", 0, Format::Asctime),
+    // MDN examples
+    v(
+        "Wed, 21 Oct 2015 07:28:00 GMT",
+        1445412480,
+        Format::ImfFixdate,
+    ),
+    v("Wed Oct 21 07:28:00 2015", 1445412480, Format::Asctime),
+    // Leap days, 2000 is a leap year as it is divisible by 400.
+    v(
+        "Tue, 29 Feb 2000 23:59:59 GMT",
+        951868799,
+        Format::ImfFixdate,
+    ),
+    v("Tuesday, 29-Feb-00 23:59:59 GMT", 951868799, Format::Rfc850),
+    v(
+        "Mon, 29 Feb 2016 12:00:00 GMT",
+        1456747200,
+        Format::ImfFixdate,
+    ),
+    // Around the turn of the millennium and the 32 bit overflow.
+    v(
+        "Fri, 31 Dec 1999 23:59:59 GMT",
+        946684799,
+        Format::ImfFixdate,
+    ),
+    v(
+        "Tue, 19 Jan 2038 03:14:08 GMT",
+        2147483648,
+        Format::ImfFixdate,
+    ),
+    // Two digit years up to 69 are in the 21st century.
+    v(
+        "Tuesday, 31-Dec-69 23:59:59 GMT",
+        3155759999,
+        Format::Rfc850,
+    ),
+    // The latest date.
+    v(
+        "Fri, 31 Dec 9999 23:59:59 GMT",
+        253402300799,
+        Format::ImfFixdate,
+    ),
+    v("Fri Dec 31 23:59:59 9999", 253402300799, Format::Asctime),
+];
+
+/// Iterate over known good HTTP dates.
+///
+/// Only available with the `test-util` feature.
+pub fn vectors() -> impl Iterator<Item = Vector> {
+    VECTORS.iter().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{vectors, Format};
+    use crate::{HttpDate, IMF_FIXDATE_LEN};
+
+    #[test]
+    fn test_vectors() {
+        for v in vectors() {
+            let date = HttpDate::parse_bytes(v.input.as_bytes()).unwrap();
+            assert_eq!(date.secs_since_epoch(), v.secs, "{}", v.input);
+            if v.format == Format::ImfFixdate {
+                let mut buf = [0; IMF_FIXDATE_LEN];
+                assert_eq!(date.format_into(&mut buf), v.input.as_bytes());
+            }
+        }
+    }
+}
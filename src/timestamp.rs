@@ -0,0 +1,152 @@
+use core::convert::TryFrom;
+
+use crate::date::MAX_SECS;
+use crate::error::{Error, ErrorKind};
+use crate::HttpDate;
+
+/// Seconds since 1970-01-01T00:00:00Z, ignoring leap seconds.
+///
+/// Use this type in place of `SystemTime` without the `std` feature.
+/// Every `HttpDate` converts to a timestamp, timestamps before 1970 or
+/// after 9999 can't be converted to an `HttpDate`.
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// # use httpdate::{HttpDate, UnixTimestamp};
+/// let date = HttpDate::try_from(UnixTimestamp(784111777)).unwrap();
+/// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert_eq!(UnixTimestamp::from(date), UnixTimestamp(784111777));
+/// assert!(HttpDate::try_from(UnixTimestamp(-1)).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct UnixTimestamp(pub i64);
+
+impl From<HttpDate> for UnixTimestamp {
+    fn from(v: HttpDate) -> UnixTimestamp {
+        UnixTimestamp(v.secs_since_epoch() as i64)
+    }
+}
+
+impl TryFrom<UnixTimestamp> for HttpDate {
+    type Error = Error;
+
+    fn try_from(v: UnixTimestamp) -> Result<HttpDate, Error> {
+        u64::try_from(v.0)
+            .ok()
+            .filter(|&secs| secs < MAX_SECS)
+            .and_then(HttpDate::from_secs_since_epoch)
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+}
+
+impl HttpDate {
+    /// Convert a JavaScript timestamp, the milliseconds since the epoch
+    /// returned by `Date.now()`, truncating it to whole seconds.
+    ///
+    /// Returns an out of range error for NaN, infinite values and times
+    /// before 1970 or after 9999.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::from_js_millis(784111777123.0).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// assert_eq!(date.to_js_millis(), 784111777000.0);
+    /// assert!(HttpDate::from_js_millis(f64::NAN).is_err());
+    /// ```
+    pub fn from_js_millis(millis: f64) -> Result<HttpDate, Error> {
+        if !(millis >= 0.0 && millis < MAX_SECS as f64 * 1000.0) {
+            return Err(Error::new(ErrorKind::Range, None));
+        }
+        HttpDate::from_secs_since_epoch((millis / 1000.0) as u64)
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+
+    /// The date as a JavaScript timestamp in milliseconds since the epoch.
+    ///
+    /// The value is exact, all dates are far below 2^53 milliseconds.
+    pub fn to_js_millis(&self) -> f64 {
+        (self.secs_since_epoch() * 1000) as f64
+    }
+
+    /// Convert milliseconds since the epoch, as used by Java and Kafka,
+    /// truncating them to whole seconds.
+    ///
+    /// Returns an out of range error for times before 1970 or after 9999.
+    ///
+    /// ```
+    /// # use httpdate::HttpDate;
+    /// let date = HttpDate::from_epoch_millis(784111777999).unwrap();
+    /// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// assert_eq!(date.to_epoch_millis(), 784111777000);
+    /// assert!(HttpDate::from_epoch_millis(-1).is_err());
+    /// ```
+    pub fn from_epoch_millis(millis: i64) -> Result<HttpDate, Error> {
+        u64::try_from(millis)
+            .ok()
+            .and_then(|ms| HttpDate::from_secs_since_epoch(ms / 1000))
+            .ok_or_else(|| Error::new(ErrorKind::Range, None))
+    }
+
+    /// The date in milliseconds since the epoch.
+    ///
+    /// This never overflows, all dates are far below `i64::MAX` milliseconds.
+    pub fn to_epoch_millis(&self) -> i64 {
+        (self.secs_since_epoch() * 1000) as i64
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::UnixTimestamp;
+    use crate::HttpDate;
+
+    #[test]
+    fn test_unix_timestamp() {
+        let d = HttpDate::try_from(UnixTimestamp(0)).unwrap();
+        assert_eq!(d, HttpDate::EPOCH);
+        assert_eq!(UnixTimestamp::from(d), UnixTimestamp(0));
+        let max = HttpDate::try_from(UnixTimestamp(253402300799)).unwrap();
+        assert_eq!(UnixTimestamp::from(max), UnixTimestamp(253402300799));
+        assert!(HttpDate::try_from(UnixTimestamp(253402300800))
+            .unwrap_err()
+            .is_out_of_range());
+        assert!(HttpDate::try_from(UnixTimestamp(i64::MIN)).is_err());
+    }
+
+    #[test]
+    fn test_js_millis() {
+        assert_eq!(HttpDate::from_js_millis(0.0).unwrap(), HttpDate::EPOCH);
+        assert_eq!(HttpDate::from_js_millis(-0.0).unwrap(), HttpDate::EPOCH);
+        assert_eq!(HttpDate::from_js_millis(999.9).unwrap(), HttpDate::EPOCH);
+        let max = HttpDate::from_js_millis(253402300799999.0).unwrap();
+        assert_eq!(max.to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+        assert_eq!(max.to_js_millis(), 253402300799000.0);
+        for &ms in &[
+            -0.5,
+            -1000.0,
+            253402300800000.0,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MAX,
+        ] {
+            assert!(HttpDate::from_js_millis(ms).unwrap_err().is_out_of_range());
+        }
+    }
+
+    #[test]
+    fn test_epoch_millis() {
+        assert_eq!(HttpDate::from_epoch_millis(0).unwrap(), HttpDate::EPOCH);
+        assert_eq!(HttpDate::from_epoch_millis(999).unwrap(), HttpDate::EPOCH);
+        let max = HttpDate::from_epoch_millis(253402300799999).unwrap();
+        assert_eq!(max.to_epoch_millis(), 253402300799000);
+        for &ms in &[-1, i64::MIN, 253402300800000, i64::MAX] {
+            assert!(HttpDate::from_epoch_millis(ms)
+                .unwrap_err()
+                .is_out_of_range());
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Wrappers for header fields that contain a single HTTP date.
+//!
+//! The wrappers parse and format header values with the rules of their
+//! header field, e.g. an invalid `Expires` value means already expired.
+//! With the `headers` feature they implement `headers::Header` so they
+//! can be used with `HeaderMapExt` or as typed header extractors.
+
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+pub use crate::RetryAfter;
+#[cfg(feature = "headers")]
+use crate::IMF_FIXDATE_LEN;
+use crate::{Error, HttpDate};
+
+macro_rules! date_header {
+    ($(#[$attr:meta])* $name:ident, $header:literal) => {
+        date_header! { $(#[$attr])* $name, $header, HttpDate::parse_bytes }
+    };
+    ($(#[$attr:meta])* $name:ident, $header:literal, $parse:expr) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+        pub struct $name(pub HttpDate);
+
+        impl From<HttpDate> for $name {
+            fn from(v: HttpDate) -> $name {
+                $name(v)
+            }
+        }
+
+        impl From<$name> for HttpDate {
+            fn from(v: $name) -> HttpDate {
+                v.0
+            }
+        }
+
+        impl $name {
+            fn parse_value(value: &[u8]) -> Result<$name, Error> {
+                let parse: fn(&[u8]) -> Result<HttpDate, Error> = $parse;
+                parse(value).map($name)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<$name, Error> {
+                $name::parse_value(s.as_bytes())
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        #[cfg(feature = "headers")]
+        impl ::headers::Header for $name {
+            fn name() -> &'static ::headers::HeaderName {
+                static NAME: ::headers::HeaderName = ::headers::HeaderName::from_static($header);
+                &NAME
+            }
+
+            fn decode<'i, I>(values: &mut I) -> Result<$name, ::headers::Error>
+            where
+                I: Iterator<Item = &'i ::headers::HeaderValue>,
+            {
+                let value = values.next().ok_or_else(::headers::Error::invalid)?;
+                if values.next().is_some() {
+                    return Err(::headers::Error::invalid());
+                }
+                $name::parse_value(value.as_bytes()).map_err(|_| ::headers::Error::invalid())
+            }
+
+            fn encode<E: Extend<::headers::HeaderValue>>(&self, values: &mut E) {
+                let mut buf = [0; IMF_FIXDATE_LEN];
+                let value = ::headers::HeaderValue::from_bytes(self.0.format_into(&mut buf))
+                    .expect("IMF-fixdate is a valid header value");
+                values.extend(::std::iter::once(value));
+            }
+        }
+    };
+}
+
+date_header! {
+    /// `Date` header field, the time the message was generated.
+    Date, "date"
+}
+
+date_header! {
+    /// `Expires` header field, the time after which the response is stale.
+    ///
+    /// An invalid date is parsed as a time in the past, the response is
+    /// already expired.
+    Expires, "expires", |value| {
+        Ok(HttpDate::parse_bytes(value).unwrap_or(HttpDate::EPOCH))
+    }
+}
+
+date_header! {
+    /// `If-Modified-Since` header field for conditional `GET` requests.
+    IfModifiedSince, "if-modified-since"
+}
+
+date_header! {
+    /// `Last-Modified` header field, the time the resource was last changed.
+    LastModified, "last-modified"
+}
+
+date_header! {
+    /// `Sunset` header field (RFC 8594), the time the resource is expected
+    /// to become unresponsive.
+    Sunset, "sunset"
+}
+
+date_header! {
+    /// `Accept-Datetime` header field (RFC 7089), the requested time of a
+    /// memento.
+    ///
+    /// Only the IMF-fixdate format is accepted.
+    AcceptDatetime, "accept-datetime", HttpDate::parse_imf_fixdate_bytes
+}
+
+date_header! {
+    /// `Memento-Datetime` header field (RFC 7089), the time a memento was
+    /// archived.
+    ///
+    /// Only the IMF-fixdate format is accepted.
+    MementoDatetime, "memento-datetime", HttpDate::parse_imf_fixdate_bytes
+}
+
+impl AcceptDatetime {
+    /// Select the memento closest to the requested time.
+    ///
+    /// Of two mementos equally far away the earlier one is selected.
+    /// Returns `None` if there are no mementos.
+    ///
+    /// ```
+    /// # use httpdate::typed::{AcceptDatetime, MementoDatetime};
+    /// let accept: AcceptDatetime = "Thu, 31 May 2007 20:35:00 GMT".parse().unwrap();
+    /// let mementos = [
+    ///     "Wed, 30 May 2007 18:47:52 GMT".parse::<MementoDatetime>().unwrap(),
+    ///     "Fri, 01 Jun 2007 00:00:00 GMT".parse().unwrap(),
+    /// ];
+    /// assert_eq!(accept.nearest(mementos.iter().copied()), Some(mementos[1]));
+    /// ```
+    pub fn nearest<I>(&self, mementos: I) -> Option<MementoDatetime>
+    where
+        I: IntoIterator<Item = MementoDatetime>,
+    {
+        let target = self.0.secs_since_epoch();
+        mementos.into_iter().min_by_key(|m| {
+            let secs = m.0.secs_since_epoch();
+            (secs.max(target) - secs.min(target), secs)
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::{AcceptDatetime, Date, Expires, LastModified, MementoDatetime, Sunset};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_from_str() {
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let lm: LastModified = "Sunday, 06-Nov-94 08:49:37 GMT".parse().unwrap();
+        assert_eq!(lm, LastModified(d));
+        assert_eq!(lm.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert!("0".parse::<Date>().is_err());
+        assert_eq!(
+            "0".parse::<Expires>(),
+            Ok(Expires(HttpDate::from(UNIX_EPOCH)))
+        );
+        assert_eq!(
+            "Sun, 06 Nov 1994 08:49:37 GMT".parse::<Expires>(),
+            Ok(Expires(d))
+        );
+        let sunset: Sunset = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert_eq!(HttpDate::from(sunset), d);
+    }
+
+    #[test]
+    fn test_memento() {
+        let accept: AcceptDatetime = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert!("Sunday, 06-Nov-94 08:49:37 GMT"
+            .parse::<AcceptDatetime>()
+            .is_err());
+        let m = |s: &str| s.parse::<MementoDatetime>().unwrap();
+        let before = m("Sun, 06 Nov 1994 08:49:27 GMT");
+        let after = m("Sun, 06 Nov 1994 08:49:47 GMT");
+        assert_eq!(accept.nearest(vec![after, before]), Some(before));
+        assert_eq!(
+            accept.nearest(vec![after, m("Sat, 05 Nov 1994 08:49:37 GMT")]),
+            Some(after)
+        );
+        assert_eq!(accept.nearest(vec![]), None);
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn test_headers() {
+        use ::headers::{HeaderMap, HeaderMapExt, HeaderValue};
+
+        let d: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let mut map = HeaderMap::new();
+        map.typed_insert(LastModified(d));
+        assert_eq!(map["last-modified"], "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(map.typed_get::<LastModified>(), Some(LastModified(d)));
+        assert_eq!(map.typed_get::<Date>(), None);
+
+        map.insert(
+            "date",
+            HeaderValue::from_static("Sunday, 06-Nov-94 08:49:37 GMT"),
+        );
+        assert_eq!(map.typed_get::<Date>(), Some(Date(d)));
+        map.append(
+            "date",
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+        assert_eq!(map.typed_get::<Date>(), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! The `Warning` header field from RFC 7234 section 5.5.
+//!
+//! A warning value may end with the date it was generated. HTTP/1.1
+//! caches must drop warning values whose date differs from the `Date`
+//! of the message, as they were added by an earlier cache and are stale.
+//!
+//! ```
+//! # use httpdate::{warning, HttpDate};
+//! let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+//! let field = r#"110 - "Response is Stale" "Sat, 05 Nov 1994 08:49:37 GMT", 299 proxy "Old""#;
+//! let kept: Vec<&str> = warning::values(field)
+//!     .filter(|v| warning::warn_date_matches(v, date))
+//!     .collect();
+//! assert_eq!(kept, [r#"299 proxy "Old""#]);
+//! ```
+
+use crate::list::split_list;
+use crate::{Error, HttpDate};
+
+/// Split a `Warning` header field into its comma separated warning values.
+///
+/// Commas in quoted strings, like the warning text or date, are skipped.
+pub fn values(field: &str) -> impl Iterator<Item = &str> {
+    split_list(field)
+}
+
+/// Parse the date of a single warning value.
+///
+/// A warning value looks like `110 anderson/1.3.37 "Response is stale"`
+/// followed by an optional date in double quotes. Returns `None` if the
+/// date is missing and an error if the value or the date is malformed.
+pub fn warn_date(value: &str) -> Result<Option<HttpDate>, Error> {
+    let s = value.trim().as_bytes();
+    // warn-code: three digits
+    if s.len() < 4 || !s[..3].iter().all(u8::is_ascii_digit) || s[3] != b' ' {
+        return Err(Error::format(0));
+    }
+    // warn-agent: host and port or a pseudonym
+    let agent_end = 4 + s[4..]
+        .iter()
+        .position(|&b| b == b' ')
+        .ok_or_else(|| Error::format(4))?;
+    // warn-text: quoted string with backslash escapes
+    let text_start = agent_end + 1;
+    let text_end = quoted_string_end(s, text_start)?;
+    let rest = &s[text_end..];
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    // warn-date: space and quoted HTTP date
+    if rest.len() < 3 || rest[0] != b' ' || rest[1] != b'"' || rest[rest.len() - 1] != b'"' {
+        return Err(Error::format(text_end));
+    }
+    HttpDate::parse_bytes(&rest[2..rest.len() - 1])
+        .map(Some)
+        .map_err(|e| e.with_input(s, text_end + 2))
+}
+
+/// Position after the quoted string starting at `start`.
+fn quoted_string_end(s: &[u8], start: usize) -> Result<usize, Error> {
+    if s.get(start) != Some(&b'"') {
+        return Err(Error::format(start));
+    }
+    let mut i = start + 1;
+    while i < s.len() {
+        match s[i] {
+            b'\\' => i += 1,
+            b'"' => return Ok(i + 1),
+            _ => {}
+        }
+        i += 1;
+    }
+    Err(Error::format(s.len()))
+}
+
+/// Whether a warning value should be kept for a message with the given `Date`.
+///
+/// Returns `true` if the value has no date or its date equals `date`.
+/// Malformed values are not kept.
+pub fn warn_date_matches(value: &str, date: HttpDate) -> bool {
+    match warn_date(value) {
+        Ok(Some(warn_date)) => warn_date == date,
+        Ok(None) => true,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use super::{values, warn_date, warn_date_matches};
+    use crate::HttpDate;
+
+    #[test]
+    fn test_warn_date() {
+        let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        assert_eq!(warn_date(r#"112 - "network down""#), Ok(None));
+        assert_eq!(
+            warn_date(r#"112 - "cache down" "Sun, 06 Nov 1994 08:49:37 GMT""#),
+            Ok(Some(date))
+        );
+        assert_eq!(
+            warn_date(r#"110 example.com:80 "say \"hi\"" "Sunday, 06-Nov-94 08:49:37 GMT""#),
+            Ok(Some(date))
+        );
+        assert!(warn_date(r#"112 - "unterminated"#).is_err());
+        assert!(warn_date(r#"11 - "short code""#).is_err());
+        assert!(warn_date(r#"112 - "text" Sun, 06 Nov 1994 08:49:37 GMT"#).is_err());
+        assert!(warn_date(r#"112 - "text" "yesterday""#).is_err());
+
+        assert!(warn_date_matches(r#"112 - "network down""#, date));
+        assert!(!warn_date_matches(
+            r#"112 - "x" "Sun, 06 Nov 1994 08:49:38 GMT""#,
+            date
+        ));
+        assert!(!warn_date_matches("garbage", date));
+    }
+
+    #[test]
+    fn test_values() {
+        let field = r#"110 - "a, b" "Sun, 06 Nov 1994 08:49:37 GMT" ,, 199 x "c\"," "#;
+        let v: Vec<&str> = values(field).collect();
+        assert_eq!(
+            v,
+            [
+                r#"110 - "a, b" "Sun, 06 Nov 1994 08:49:37 GMT""#,
+                r#"199 x "c\",""#
+            ]
+        );
+        assert_eq!(values("").count(), 0);
+    }
+}
@@ -0,0 +1,24 @@
+//! Conversions from and to `web_time::SystemTime`, enabled with the `wasm`
+//! feature on `wasm32-unknown-unknown`.
+//!
+//! On all other targets `web_time::SystemTime` is the standard library type.
+
+use web_time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::HttpDate;
+
+impl From<SystemTime> for HttpDate {
+    fn from(v: SystemTime) -> HttpDate {
+        let dur = v
+            .duration_since(UNIX_EPOCH)
+            .expect("all times should be after the epoch");
+        HttpDate::from_secs_since_epoch(dur.as_secs())
+            .unwrap_or_else(|| panic!("date must be before year 9999"))
+    }
+}
+
+impl From<HttpDate> for SystemTime {
+    fn from(v: HttpDate) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(v.secs_since_epoch())
+    }
+}
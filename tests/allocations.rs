@@ -0,0 +1,72 @@
+//! Check that parsing, comparing and formatting dates never allocate.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use httpdate::{HttpDate, IMF_FIXDATE_LEN};
+
+/// Counts the allocations made by the current thread.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_round_trip() {
+    let n = allocations(|| {
+        let a: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let b: HttpDate = "Sunday, 06-Nov-94 08:49:37 GMT".parse().unwrap();
+        let c: HttpDate = "Sun Nov  6 08:49:38 1994".parse().unwrap();
+        assert!(a == b && b < c);
+        let mut buf = [0; IMF_FIXDATE_LEN];
+        assert_eq!(c.format_into(&mut buf), b"Sun, 06 Nov 1994 08:49:38 GMT");
+    });
+    assert_eq!(n, 0);
+}
+
+#[test]
+fn test_errors() {
+    let n = allocations(|| {
+        let err = "Sun, 31 Feb 1994 08:49:37 GMT"
+            .parse::<HttpDate>()
+            .unwrap_err();
+        assert!(err.is_invalid_date());
+        assert!("garbage".parse::<HttpDate>().is_err());
+    });
+    assert_eq!(n, 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_date_header_cache() {
+    let d1: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    let d2: HttpDate = "Sun, 06 Nov 1994 08:49:38 GMT".parse().unwrap();
+    let mut cache = httpdate::DateHeaderCache::new();
+    cache.get_at(d1);
+    let n = allocations(|| {
+        assert_eq!(cache.get_at(d1), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(cache.get_at(d2), "Sun, 06 Nov 1994 08:49:38 GMT");
+    });
+    assert_eq!(n, 0);
+}